@@ -1,77 +1,263 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+use crate::util::checked::{self, ArithmeticError, CheckedOps};
 
 pub mod benchmarks;
+#[cfg(feature = "bigint")]
+pub mod bigint;
+pub mod comparison;
+
+// Location IDs of any integer width, signed ones allowing negative IDs
+pub trait ListId: CheckedOps + Ord + Hash + FromStr + Default + TryFrom<usize> {}
+
+impl<T> ListId for T where T: CheckedOps + Ord + Hash + FromStr + Default + TryFrom<usize> {}
+
+// What to do with the IDs left without a pair when the lists have different lengths
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LeftoverPolicy {
+  Strict,
+  Truncate,
+  PadWithZero,
+}
+
+#[derive(Debug)]
+pub enum ListsError {
+  Io(io::Error),
+  BadId { line: usize, value: String },
+  MalformedLine { line: usize },
+  UnequalLengths { left: usize, right: usize },
+  Arithmetic(ArithmeticError),
+}
+
+impl fmt::Display for ListsError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ListsError::Io(err) => write!(f, "failed to read lists: {err}"),
+      ListsError::BadId { line, value } => write!(f, "line {line}: `{value}` is not a valid ID"),
+      ListsError::MalformedLine { line } => write!(f, "line {line}: expected at most two IDs"),
+      ListsError::UnequalLengths { left, right } => write!(f, "lists lengths differ: {left} vs {right}"),
+      ListsError::Arithmetic(err) => write!(f, "{err}"),
+    }
+  }
+}
+
+impl std::error::Error for ListsError {}
+
+impl From<io::Error> for ListsError {
+  fn from(err: io::Error) -> Self {
+    ListsError::Io(err)
+  }
+}
 
-fn parse_input(input: &str) -> (Vec<u32>, Vec<u32>) {
-  let mut list1: Vec<u32> = Vec::new();
-  let mut list2: Vec<u32> = Vec::new();
-
-  input
-    .split("\n")
-    .filter(|x| !x.is_empty())
-    .map(|line| {
-      line
-        .split_whitespace()
-        .map(|x| x.parse::<u32>().unwrap())
-        .collect::<Vec<_>>()
-    })
-    .for_each(|line_item| {
-      list1.push(line_item[0]);
-      list2.push(line_item[1]);
-    });
-  (list1, list2)
-}
-
-fn calc_total_distance(mut list1: Vec<u32>, mut list2: Vec<u32>) -> u32 {
-  list1.sort();
-  list2.sort();
-
-  let range_iter = 0..list1.len();
-  let total_dist = range_iter.fold(0, |acc, i| {
-    let (i1, i2) = (list1[i], list2[i]);
-    let dist = match i1.cmp(&i2) {
-      std::cmp::Ordering::Greater => i1 - i2,
-      std::cmp::Ordering::Less => i2 - i1,
-      std::cmp::Ordering::Equal => 0,
+impl From<ArithmeticError> for ListsError {
+  fn from(err: ArithmeticError) -> Self {
+    ListsError::Arithmetic(err)
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Lists<T> {
+  pub left: Vec<T>,
+  pub right: Vec<T>,
+}
+
+// Reads the two columns line by line, reusing a single line buffer; a line with a single ID belongs to the left list,
+// unless it's indented, in which case the left column is the empty one
+pub fn read_lists<T: ListId, R: BufRead>(mut reader: R) -> Result<Lists<T>, ListsError> {
+  let mut lists: Lists<T> = Lists {
+    left: vec![],
+    right: vec![],
+  };
+  let mut line = String::new();
+  let mut line_no = 0;
+
+  while reader.read_line(&mut line)? > 0 {
+    line_no += 1;
+    let parse = |value: &str| {
+      value.parse::<T>().map_err(|_| ListsError::BadId {
+        line: line_no,
+        value: value.to_string(),
+      })
     };
-    // println!("{}: {} {} {}", i, list1[i], list2[i], dist);
-    return acc + dist;
-  });
 
-  total_dist
+    let mut ids = line.split_whitespace();
+    match (ids.next(), ids.next(), ids.next()) {
+      (None, _, _) => {}
+      (Some(left), Some(right), None) => {
+        lists.left.push(parse(left)?);
+        lists.right.push(parse(right)?);
+      }
+      (Some(id), None, _) if line.starts_with(char::is_whitespace) => lists.right.push(parse(id)?),
+      (Some(id), None, _) => lists.left.push(parse(id)?),
+      _ => return Err(ListsError::MalformedLine { line: line_no }),
+    }
+    line.clear();
+  }
+  Ok(lists)
 }
 
-fn calc_similarity_score(list1: &Vec<u32>, list2: &Vec<u32>) -> u32 {
-  list1.iter().fold(0, |acc, item| {
-    let count = list2.iter().filter(|i| item == *i).count() as u32;
-    let similarity = item * count;
-    // println!("{} => {} : {}", item, count, similarity);
-    return acc + similarity;
+fn parse_input<T: ListId>(input: &str) -> Result<Lists<T>, ListsError> {
+  read_lists(input.as_bytes())
+}
+
+fn pair_sorted<T: ListId>(mut lists: Lists<T>, policy: LeftoverPolicy) -> Result<Vec<(T, T)>, ListsError> {
+  let (left_len, right_len) = (lists.left.len(), lists.right.len());
+  match policy {
+    _ if left_len == right_len => {}
+    LeftoverPolicy::Strict => {
+      return Err(ListsError::UnequalLengths {
+        left: left_len,
+        right: right_len,
+      })
+    }
+    // ## zip drops the largest IDs of the longer list
+    LeftoverPolicy::Truncate => {}
+    // ## the zeros are sorted along with the IDs and pair up by rank, mid-list when there are negative IDs
+    LeftoverPolicy::PadWithZero => {
+      let len = std::cmp::max(left_len, right_len);
+      lists.left.resize(len, T::default());
+      lists.right.resize(len, T::default());
+    }
+  }
+
+  lists.left.sort();
+  lists.right.sort();
+  Ok(lists.left.into_iter().zip(lists.right).collect())
+}
+
+fn calc_distance<T: ListId>(i1: T, i2: T) -> Result<T, ArithmeticError> {
+  // ## subtracting the smaller ID never underflows unsigned IDs, but may still overflow signed ones
+  match i1.cmp(&i2) {
+    std::cmp::Ordering::Greater => i1.try_sub(i2),
+    std::cmp::Ordering::Less => i2.try_sub(i1),
+    std::cmp::Ordering::Equal => Ok(T::default()),
+  }
+}
+
+fn calc_total_distance<T: ListId>(pairs: &Vec<(T, T)>) -> Result<T, ArithmeticError> {
+  pairs.iter().try_fold(T::default(), |acc, &(i1, i2)| {
+    let dist = calc_distance(i1, i2)?;
+    acc.try_add(dist)
   })
 }
 
-fn calc_similarity_score_fast(list1: &Vec<u32>, list2: &Vec<u32>) -> u32 {
-  let mut counts: HashMap<u32, u32> = HashMap::new();
-  list1.iter().for_each(|list_item| {
-    counts.insert(*list_item, 0);
-  });
+fn calc_similarity_score<T: ListId>(list1: &Vec<T>, list2: &Vec<T>) -> Result<T, ArithmeticError> {
+  list1.iter().try_fold(T::default(), |acc, item| {
+    let count: T = checked::try_convert(list2.iter().filter(|i| item == *i).count())?;
+    let similarity = item.try_mul(count)?;
+    // println!("{} => {} : {}", item, count, similarity);
+    acc.try_add(similarity)
+  })
+}
 
+fn calc_similarity_score_fast<T: ListId>(list1: &Vec<T>, list2: &Vec<T>) -> Result<T, ArithmeticError> {
+  let mut counts: HashMap<T, usize> = HashMap::new();
   list2.iter().for_each(|list_item| {
-    counts.entry(*list_item).and_modify(|count| *count += 1);
+    *counts.entry(*list_item).or_insert(0) += 1;
   });
 
-  counts.into_iter().fold(0, |acc, (item, count)| {
-    let similarity = item * count;
-    return acc + similarity;
+  // ## every left item counts, duplicates included
+  list1.iter().try_fold(T::default(), |acc, item| {
+    let count = counts.get(item).copied().unwrap_or(0);
+    let similarity = item.try_mul(checked::try_convert(count)?)?;
+    acc.try_add(similarity)
   })
 }
 
-pub fn part1(input: &str) -> u32 {
-  let (list1, list2) = parse_input(input);
-  calc_total_distance(list1, list2)
+pub fn total_distance<T: ListId, R: BufRead>(reader: R, policy: LeftoverPolicy) -> Result<T, ListsError> {
+  let pairs = pair_sorted(read_lists(reader)?, policy)?;
+  Ok(calc_total_distance(&pairs)?)
+}
+
+pub fn similarity_score<T: ListId, R: BufRead>(reader: R) -> Result<T, ListsError> {
+  let lists = read_lists(reader)?;
+  Ok(calc_similarity_score_fast(&lists.left, &lists.right)?)
+}
+
+pub fn part1(input: &str) -> Result<u32, ListsError> {
+  let lists = parse_input(input)?;
+  let pairs = pair_sorted(lists, LeftoverPolicy::Strict)?;
+  Ok(calc_total_distance(&pairs)?)
+}
+
+pub fn part2(input: &str) -> Result<u32, ListsError> {
+  let lists = parse_input(input)?;
+  Ok(calc_similarity_score_fast(&lists.left, &lists.right)?)
 }
 
-pub fn part2(input: &str) -> u32 {
-  let (list1, list2) = parse_input(input);
-  calc_similarity_score_fast(&list1, &list2)
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+  #[test]
+  fn test_example() {
+    assert_eq!(part1(EXAMPLE).unwrap(), 11);
+
+    let lists: Lists<u32> = parse_input(EXAMPLE).unwrap();
+    assert_eq!(calc_similarity_score(&lists.left, &lists.right), Ok(31));
+    assert_eq!(part2(EXAMPLE).unwrap(), 31);
+    assert_eq!(similarity_score::<u32, _>(EXAMPLE.as_bytes()).unwrap(), 31);
+  }
+
+  #[test]
+  fn test_overflow_is_reported() {
+    let input = "4294967295   0\n4294967295   0\n";
+    assert!(matches!(part1(input), Err(ListsError::Arithmetic(ArithmeticError::Overflow(_)))));
+
+    let input = "2147483648   2147483648\n0   2147483648\n";
+    assert!(matches!(part2(input), Err(ListsError::Arithmetic(ArithmeticError::Overflow(_)))));
+  }
+
+  #[test]
+  fn test_generic_ids() {
+    let input = "-3   4\n4   -3\n";
+    assert_eq!(total_distance::<i64, _>(input.as_bytes(), LeftoverPolicy::Strict).unwrap(), 0);
+
+    let input = "-5   5\n";
+    assert_eq!(total_distance::<i64, _>(input.as_bytes(), LeftoverPolicy::Strict).unwrap(), 10);
+    assert_eq!(total_distance::<u64, _>(EXAMPLE.as_bytes(), LeftoverPolicy::Strict).unwrap(), 11);
+
+    let input = "-9223372036854775808   9223372036854775807\n";
+    let result = total_distance::<i64, _>(input.as_bytes(), LeftoverPolicy::Strict);
+    assert!(matches!(result, Err(ListsError::Arithmetic(ArithmeticError::Overflow(_)))));
+  }
+
+  #[test]
+  fn test_leftover_policies() {
+    let input = "1   2\n7\n   4\n   6\n";
+    let lists: Lists<i64> = read_lists(input.as_bytes()).unwrap();
+    assert_eq!(lists.left, vec![1, 7]);
+    assert_eq!(lists.right, vec![2, 4, 6]);
+
+    let result = total_distance::<i64, _>(input.as_bytes(), LeftoverPolicy::Strict);
+    assert!(matches!(result, Err(ListsError::UnequalLengths { left: 2, right: 3 })));
+    // ## sorted pairs (1, 2), (7, 4), leftover 6
+    let result = total_distance::<i64, _>(input.as_bytes(), LeftoverPolicy::Truncate);
+    assert_eq!(result.unwrap(), 4);
+    // ## padded to [0, 1, 7] and [2, 4, 6]: pairs (0, 2), (1, 4), (7, 6)
+    let result = total_distance::<i64, _>(input.as_bytes(), LeftoverPolicy::PadWithZero);
+    assert_eq!(result.unwrap(), 6);
+    let result = total_distance::<i64, _>("5\n-3\n   1\n".as_bytes(), LeftoverPolicy::PadWithZero);
+    assert_eq!(result.unwrap(), 3 + 4);
+  }
+
+  #[test]
+  fn test_malformed_input() {
+    assert!(matches!(part1("1   2\n3   x\n"), Err(ListsError::BadId { line: 2, .. })));
+    assert!(matches!(part1("1   2\n3\n"), Err(ListsError::UnequalLengths { left: 2, right: 1 })));
+    assert!(matches!(part2("1   2   3\n"), Err(ListsError::MalformedLine { line: 1 })));
+
+    let result = read_lists::<u32, _>("1   2\n3   x\n".as_bytes());
+    assert!(matches!(result, Err(ListsError::BadId { line: 2, .. })));
+    let result = read_lists::<u32, _>("1   2   3\n".as_bytes());
+    assert!(matches!(result, Err(ListsError::MalformedLine { line: 1 })));
+    let result = read_lists::<u32, _>("-1   2\n".as_bytes());
+    assert!(matches!(result, Err(ListsError::BadId { line: 1, .. })));
+  }
 }
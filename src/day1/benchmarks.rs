@@ -5,18 +5,22 @@ use super::*;
 #[derive(Debug)]
 enum BenchImpl {
   Part1Std,
+  Part1Stream,
   Part2Std,
   Part2Fast,
+  Part2Stream,
 }
 
 fn run_benchmark(input: &str, fn_impl: BenchImpl) {
-  let (list1, list2) = parse_input(input);
+  let lists: Lists<u32> = parse_input(input).unwrap();
 
   let now = std::time::Instant::now();
   let result = match fn_impl {
-    BenchImpl::Part1Std => calc_total_distance(list1, list2),
-    BenchImpl::Part2Std => calc_similarity_score(&list1, &list2),
-    BenchImpl::Part2Fast => calc_similarity_score_fast(&list1, &list2),
+    BenchImpl::Part1Std => calc_total_distance(&pair_sorted(lists, LeftoverPolicy::Strict).unwrap()).unwrap(),
+    BenchImpl::Part1Stream => total_distance(input.as_bytes(), LeftoverPolicy::Strict).unwrap(),
+    BenchImpl::Part2Std => calc_similarity_score(&lists.left, &lists.right).unwrap(),
+    BenchImpl::Part2Fast => calc_similarity_score_fast(&lists.left, &lists.right).unwrap(),
+    BenchImpl::Part2Stream => similarity_score(input.as_bytes()).unwrap(),
   };
   let elapsed = now.elapsed();
   println!("[{fn_impl:#?}] Elapsed {elapsed:.2?}; Result: {result}");
@@ -25,6 +29,8 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
 pub fn run(input: &str, env: Env) {
   bench_spacer(env);
   run_benchmark(input, BenchImpl::Part1Std);
+  run_benchmark(input, BenchImpl::Part1Stream);
   run_benchmark(input, BenchImpl::Part2Std);
   run_benchmark(input, BenchImpl::Part2Fast);
+  run_benchmark(input, BenchImpl::Part2Stream);
 }
@@ -0,0 +1,88 @@
+use num_bigint::BigInt;
+use std::collections::HashMap;
+
+use super::ListsError;
+
+// Arbitrary-precision variant for scaled-up stress inputs, where IDs or their sums don't fit into `u32`
+fn parse_input(input: &str) -> Result<(Vec<BigInt>, Vec<BigInt>), ListsError> {
+  let mut list1: Vec<BigInt> = Vec::new();
+  let mut list2: Vec<BigInt> = Vec::new();
+
+  input
+    .lines()
+    .enumerate()
+    .filter(|(_, x)| !x.is_empty())
+    .try_for_each(|(idx, line)| {
+      let parse = |value: &str| {
+        value.parse::<BigInt>().map_err(|_| ListsError::BadId {
+          line: idx + 1,
+          value: value.to_string(),
+        })
+      };
+      match line.split_whitespace().collect::<Vec<&str>>()[..] {
+        [id1, id2] => {
+          list1.push(parse(id1)?);
+          list2.push(parse(id2)?);
+          Ok(())
+        }
+        _ => Err(ListsError::MalformedLine { line: idx + 1 }),
+      }
+    })?;
+  Ok((list1, list2))
+}
+
+fn calc_total_distance(mut list1: Vec<BigInt>, mut list2: Vec<BigInt>) -> BigInt {
+  list1.sort();
+  list2.sort();
+
+  list1
+    .iter()
+    .zip(list2.iter())
+    .map(|(i1, i2)| match i1 > i2 {
+      true => i1 - i2,
+      false => i2 - i1,
+    })
+    .sum()
+}
+
+fn calc_similarity_score(list1: &Vec<BigInt>, list2: &Vec<BigInt>) -> BigInt {
+  let mut counts: HashMap<&BigInt, usize> = HashMap::new();
+  list2
+    .iter()
+    .for_each(|list_item| *counts.entry(list_item).or_insert(0) += 1);
+
+  list1
+    .iter()
+    .map(|item| item * BigInt::from(*counts.get(item).unwrap_or(&0)))
+    .sum()
+}
+
+pub fn part1(input: &str) -> Result<BigInt, ListsError> {
+  let (list1, list2) = parse_input(input)?;
+  Ok(calc_total_distance(list1, list2))
+}
+
+pub fn part2(input: &str) -> Result<BigInt, ListsError> {
+  let (list1, list2) = parse_input(input)?;
+  Ok(calc_similarity_score(&list1, &list2))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_beyond_u32() {
+    let input = "4294967295   0\n4294967295   0\n";
+    assert_eq!(part1(input).unwrap(), BigInt::from(8589934590_u64));
+
+    let input = "2147483648   2147483648\n0   2147483648\n";
+    assert_eq!(part2(input).unwrap(), BigInt::from(4294967296_u64));
+  }
+
+  #[test]
+  fn test_malformed_input() {
+    assert!(matches!(part1("1   x\n"), Err(ListsError::BadId { line: 1, .. })));
+    assert!(matches!(part2("1   2\n3\n"), Err(ListsError::MalformedLine { line: 2 })));
+  }
+}
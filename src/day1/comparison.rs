@@ -0,0 +1,296 @@
+use std::collections::{BTreeSet, HashMap};
+use std::io::BufRead;
+
+use crate::util::checked::ArithmeticError;
+
+use super::{calc_distance, pair_sorted, read_lists, LeftoverPolicy, ListId, Lists, ListsError};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matching<T> {
+  pub left: T,
+  pub right: T,
+  pub distance: T,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EarthMovers<T> {
+  pub total: T,
+  pub matching: Vec<Matching<T>>,
+}
+
+// Both lists treated as multisets of IDs, kept sorted so every metric is computed from the same parsed input
+#[derive(Debug, Clone)]
+pub struct ListComparison<T> {
+  left: Vec<T>,
+  right: Vec<T>,
+  left_counts: HashMap<T, usize>,
+  right_counts: HashMap<T, usize>,
+}
+
+fn count_ids<T: ListId>(list: &Vec<T>) -> HashMap<T, usize> {
+  let mut counts: HashMap<T, usize> = HashMap::new();
+  list.iter().for_each(|id| *counts.entry(*id).or_insert(0) += 1);
+  counts
+}
+
+impl<T: ListId> ListComparison<T> {
+  pub fn new(mut lists: Lists<T>) -> Self {
+    lists.left.sort();
+    lists.right.sort();
+    Self {
+      left_counts: count_ids(&lists.left),
+      right_counts: count_ids(&lists.right),
+      left: lists.left,
+      right: lists.right,
+    }
+  }
+
+  pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, ListsError> {
+    Ok(Self::new(read_lists(reader)?))
+  }
+
+  // ## every distinct ID repeated `f(left count, right count)` times, in ascending order
+  fn combine_counts<F>(&self, f: F) -> Vec<T>
+  where
+    F: Fn(usize, usize) -> usize,
+  {
+    let ids: BTreeSet<&T> = self.left_counts.keys().chain(self.right_counts.keys()).collect();
+    ids
+      .into_iter()
+      .flat_map(|id| {
+        let left_count = self.left_counts.get(id).copied().unwrap_or(0);
+        let right_count = self.right_counts.get(id).copied().unwrap_or(0);
+        std::iter::repeat_n(*id, f(left_count, right_count))
+      })
+      .collect()
+  }
+
+  pub fn intersection(&self) -> Vec<T> {
+    self.combine_counts(|l, r| l.min(r))
+  }
+
+  pub fn union(&self) -> Vec<T> {
+    self.combine_counts(|l, r| l.max(r))
+  }
+
+  // IDs of the left list without a copy in the right one, repeated as many times as they're unmatched
+  pub fn left_difference(&self) -> Vec<T> {
+    self.combine_counts(|l, r| l.saturating_sub(r))
+  }
+
+  pub fn right_difference(&self) -> Vec<T> {
+    self.combine_counts(|l, r| r.saturating_sub(l))
+  }
+
+  // Multiset Jaccard index; two empty lists are considered identical
+  pub fn jaccard_similarity(&self) -> f64 {
+    let union_size = self.union().len();
+    match union_size {
+      0 => 1.0,
+      _ => self.intersection().len() as f64 / union_size as f64,
+    }
+  }
+
+  // Optimal transport between the two lists, with unit masses on a line:
+  // - equal lengths (or padded with zeros) => pairing the sorted lists
+  // - truncated => every ID of the shorter list goes to a distinct ID of the longer one, see `match_unbalanced`
+  pub fn earth_movers_distance(&self, policy: LeftoverPolicy) -> Result<EarthMovers<T>, ListsError> {
+    let pairs: Vec<(T, T)> = match policy {
+      LeftoverPolicy::Truncate if self.left.len() != self.right.len() => match self.left.len() < self.right.len() {
+        true => match_unbalanced(&self.left, &self.right)?,
+        false => match_unbalanced(&self.right, &self.left)?
+          .into_iter()
+          .map(|(right, left)| (left, right))
+          .collect(),
+      },
+      _ => {
+        let lists = Lists {
+          left: self.left.clone(),
+          right: self.right.clone(),
+        };
+        pair_sorted(lists, policy)?
+      }
+    };
+    let matching = pairs
+      .into_iter()
+      .map(|(left, right)| {
+        Ok(Matching {
+          left,
+          right,
+          distance: calc_distance(left, right)?,
+        })
+      })
+      .collect::<Result<Vec<Matching<T>>, ArithmeticError>>()?;
+    let total = matching
+      .iter()
+      .try_fold(T::default(), |acc, m| acc.try_add(m.distance))?;
+    Ok(EarthMovers { total, matching })
+  }
+
+  // Distinct IDs appearing in the left list only
+  pub fn only_in_left(&self) -> Vec<T> {
+    self.combine_counts(|l, r| (l > 0 && r == 0) as usize)
+  }
+
+  pub fn only_in_right(&self) -> Vec<T> {
+    self.combine_counts(|l, r| (r > 0 && l == 0) as usize)
+  }
+}
+
+// Cheapest matching of every (sorted) `shorter` ID to a distinct (sorted) `longer` one. Optimal matchings on a line never
+// cross, so the cost of matching `shorter[..i]` within `longer[..j]` either leaves `longer[j - 1]` out or pairs it with
+// `shorter[i - 1]`. Not crossing also means `shorter[i]` pairs within `longer[i..=i + m - n]`, so only that band of
+// `j`s is kept: `costs[i * band + k]` is the cost for `j = i + k`, in O(n * (m - n + 1)) time and memory
+fn match_unbalanced<T: ListId>(shorter: &[T], longer: &[T]) -> Result<Vec<(T, T)>, ArithmeticError> {
+  let (n, m) = (shorter.len(), longer.len());
+  let band = m - n + 1;
+  let mut costs: Vec<T> = vec![T::default(); (n + 1) * band];
+
+  for i in 1..=n {
+    for k in 0..band {
+      let paired = costs[(i - 1) * band + k].try_add(calc_distance(shorter[i - 1], longer[i + k - 1])?)?;
+      costs[i * band + k] = match k {
+        0 => paired,
+        _ => std::cmp::min(costs[i * band + k - 1], paired),
+      };
+    }
+  }
+
+  let mut pairs: Vec<(T, T)> = vec![];
+  let (mut i, mut k) = (n, band - 1);
+  while i > 0 {
+    match k > 0 && costs[i * band + k] == costs[i * band + k - 1] {
+      true => k -= 1,
+      false => {
+        pairs.push((shorter[i - 1], longer[i + k - 1]));
+        i -= 1;
+      }
+    }
+  }
+  pairs.reverse();
+  Ok(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+  #[test]
+  fn test_multiset_operations() {
+    let comparison: ListComparison<u32> = ListComparison::from_reader(EXAMPLE.as_bytes()).unwrap();
+    assert_eq!(comparison.intersection(), vec![3, 3, 3, 4]);
+    assert_eq!(comparison.left_difference(), vec![1, 2]);
+    assert_eq!(comparison.right_difference(), vec![5, 9]);
+    assert_eq!(comparison.jaccard_similarity(), 0.5);
+    assert_eq!(comparison.only_in_left(), vec![1, 2]);
+    assert_eq!(comparison.only_in_right(), vec![5, 9]);
+
+    let comparison: ListComparison<i64> = ListComparison::from_reader("3   3\n3\n-3\n".as_bytes()).unwrap();
+    assert_eq!(comparison.left_difference(), vec![-3, 3]);
+    assert_eq!(comparison.only_in_left(), vec![-3]);
+    assert!(comparison.only_in_right().is_empty());
+  }
+
+  #[test]
+  fn test_earth_movers_distance() {
+    let comparison: ListComparison<u32> = ListComparison::from_reader(EXAMPLE.as_bytes()).unwrap();
+    let emd = comparison.earth_movers_distance(LeftoverPolicy::Strict).unwrap();
+    assert_eq!(emd.total, 11);
+    let distances: Vec<u32> = emd.matching.iter().map(|m| m.distance).collect();
+    assert_eq!(distances, vec![2, 1, 0, 1, 2, 5]);
+    assert_eq!(
+      emd.matching[5],
+      Matching {
+        left: 4,
+        right: 9,
+        distance: 5
+      }
+    );
+
+    let comparison: ListComparison<u32> = ListComparison::from_reader("1   2\n5\n".as_bytes()).unwrap();
+    let result = comparison.earth_movers_distance(LeftoverPolicy::Strict);
+    assert!(matches!(result, Err(ListsError::UnequalLengths { left: 2, right: 1 })));
+  }
+
+  #[test]
+  fn test_earth_movers_distance_unequal_lengths() {
+    let total = |input: &str, policy: LeftoverPolicy| {
+      let comparison: ListComparison<i64> = ListComparison::from_reader(input.as_bytes()).unwrap();
+      comparison.earth_movers_distance(policy).unwrap().total
+    };
+    // ## left [1, 100], right [100]: 1 is left out
+    assert_eq!(total("1   100\n100\n", LeftoverPolicy::Truncate), 0);
+    assert_eq!(total("100   1\n   100\n", LeftoverPolicy::Truncate), 0);
+    // ## ... or padded to [0, 100]
+    assert_eq!(total("1   100\n100\n", LeftoverPolicy::PadWithZero), 1);
+    // ## left [1, 5], right [2]: padded to [0, 2]
+    assert_eq!(total("1   2\n5\n", LeftoverPolicy::PadWithZero), 4);
+    // ## left [1, 7], right [2, 4, 6]: 1 => 2, 7 => 6
+    assert_eq!(total("1   2\n7\n   4\n   6\n", LeftoverPolicy::Truncate), 2);
+  }
+
+  #[test]
+  fn test_earth_movers_distance_truncate_is_optimal() {
+    // ## every way of picking distinct longer-list IDs for the shorter list, in every order
+    fn brute_force(shorter: &[i64], longer: &[i64]) -> i64 {
+      match shorter.split_first() {
+        None => 0,
+        Some((&id, rest)) => (0..longer.len())
+          .map(|idx| {
+            let mut remaining = longer.to_vec();
+            let picked = remaining.remove(idx);
+            (id - picked).abs() + brute_force(rest, &remaining)
+          })
+          .min()
+          .unwrap(),
+      }
+    }
+
+    let lists: Vec<Vec<i64>> = vec![
+      vec![],
+      vec![3],
+      vec![9, -2],
+      vec![1, 4, 4],
+      vec![8, 0, 5, -7],
+      vec![2, 6, 3, 11, -1],
+    ];
+    lists.iter().for_each(|left| {
+      lists.iter().for_each(|right| {
+        let comparison = ListComparison::new(Lists {
+          left: left.clone(),
+          right: right.clone(),
+        });
+        let emd = comparison.earth_movers_distance(LeftoverPolicy::Truncate).unwrap();
+        let expected = match left.len() <= right.len() {
+          true => brute_force(left, right),
+          false => brute_force(right, left),
+        };
+        assert_eq!(emd.total, expected, "{left:?} vs {right:?}");
+        assert_eq!(emd.matching.len(), std::cmp::min(left.len(), right.len()));
+        assert_eq!(emd.matching.iter().map(|m| m.distance).sum::<i64>(), expected);
+      });
+    });
+  }
+
+  #[test]
+  fn test_earth_movers_distance_large_unequal_lengths() {
+    // ## every left ID has a right one next to it, the two extra right IDs are far away
+    let left: Vec<i64> = (0..1_000_000).map(|i| i * 3).collect();
+    let right: Vec<i64> = left.iter().map(|id| id + 1).chain([-1_000, 10_000_000]).collect();
+    let comparison = ListComparison::new(Lists { left, right });
+
+    let emd = comparison.earth_movers_distance(LeftoverPolicy::Truncate).unwrap();
+    assert_eq!(emd.total, 1_000_000);
+    assert_eq!(emd.matching.len(), 1_000_000);
+    assert!(emd.matching.iter().all(|m| m.right == m.left + 1));
+  }
+
+  #[test]
+  fn test_empty_lists() {
+    let comparison: ListComparison<u32> = ListComparison::from_reader("".as_bytes()).unwrap();
+    assert_eq!(comparison.jaccard_similarity(), 1.0);
+    assert!(comparison.intersection().is_empty());
+  }
+}
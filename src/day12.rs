@@ -1,6 +1,10 @@
 use std::collections::HashMap;
+use std::io;
 
-use itertools::Itertools;
+use crate::util::dsu;
+use crate::util::render;
+
+pub mod benchmarks;
 
 #[derive(Debug, Eq, Hash, PartialEq)]
 enum Border {
@@ -27,7 +31,7 @@ struct Plot {
   plant: char,
   coords: Coords,
   fence: HashMap<Border, bool>,
-  area: Option<u16>,
+  area: Option<usize>,
 }
 
 impl Plot {
@@ -67,7 +71,7 @@ fn have_same_plants(plots: &Vec<Vec<Plot>>, coords1: &Coords, coords2: &Coords)
   plots[coords1.x][coords1.y].plant == plots[coords2.x][coords2.y].plant
 }
 
-fn assign_fences_and_area(plots: &mut Vec<Vec<Plot>>, plot_coords: &Coords, area_id: u16, garden_size: &Coords) {
+fn assign_fences_and_area(plots: &mut Vec<Vec<Plot>>, plot_coords: &Coords, area_id: usize, garden_size: &Coords) {
   let (x, y) = (plot_coords.x, plot_coords.y);
   let has_north = x > 0;
   let has_south = x < garden_size.x - 1;
@@ -129,7 +133,7 @@ fn assign_fences_and_area(plots: &mut Vec<Vec<Plot>>, plot_coords: &Coords, area
 
 fn calc_fences_and_areas(garden: &mut Garden) {
   let plots = &mut garden.plots;
-  let mut area_id: u16 = 0;
+  let mut area_id: usize = 0;
 
   for x in 0..garden.size.x {
     for y in 0..garden.size.y {
@@ -144,8 +148,31 @@ fn calc_fences_and_areas(garden: &mut Garden) {
   }
 }
 
-fn get_regions(garden: &Garden) -> HashMap<u16, Vec<&Plot>> {
-  let mut regions: HashMap<u16, Vec<&Plot>> = HashMap::new();
+fn calc_fences_and_areas_dsu(garden: &mut Garden) {
+  let (size_x, size_y) = (garden.size.x, garden.size.y);
+  let labels = dsu::label_grid_components(&garden.plots, |p1, p2| p1.plant == p2.plant);
+
+  for x in 0..size_x {
+    for y in 0..size_y {
+      let plant = garden.plots[x][y].plant;
+      let is_fenced = |nx: usize, ny: usize| garden.plots[nx][ny].plant != plant;
+
+      let fence = HashMap::from([
+        (Border::N, x == 0 || is_fenced(x - 1, y)),
+        (Border::E, y == size_y - 1 || is_fenced(x, y + 1)),
+        (Border::S, x == size_x - 1 || is_fenced(x + 1, y)),
+        (Border::W, y == 0 || is_fenced(x, y - 1)),
+      ]);
+
+      let plot = &mut garden.plots[x][y];
+      plot.fence = fence;
+      plot.area = Some(labels[x][y] + 1);
+    }
+  }
+}
+
+fn get_regions(garden: &Garden) -> HashMap<usize, Vec<&Plot>> {
+  let mut regions: HashMap<usize, Vec<&Plot>> = HashMap::new();
   garden
     .plots
     .iter()
@@ -160,7 +187,7 @@ fn get_regions(garden: &Garden) -> HashMap<u16, Vec<&Plot>> {
   regions
 }
 
-fn calculate_price_by_perimeter(regions: &HashMap<u16, Vec<&Plot>>) -> usize {
+fn calculate_price_by_perimeter(regions: &HashMap<usize, Vec<&Plot>>) -> usize {
   regions
     .into_iter()
     .map(|(_region, plots)| {
@@ -176,29 +203,47 @@ fn calculate_price_by_perimeter(regions: &HashMap<u16, Vec<&Plot>>) -> usize {
     .sum()
 }
 
-fn _calculate_price_by_sides_count(regions: &HashMap<u16, Vec<&Plot>>) -> usize {
+fn count_region_sides(plots: &Vec<&Plot>) -> usize {
+  let region_plots: HashMap<(usize, usize), &Plot> = plots.iter().map(|&p| ((p.coords.x, p.coords.y), p)).collect();
+  let has_fence = |x: usize, y: usize, border: &Border| match region_plots.get(&(x, y)) {
+    Some(plot) => plot.fence[border],
+    None => false,
+  };
+
+  // ## a fence segment starts a new side, unless the preceding plot along the side (to the west for
+  // ## north/south borders, to the north for east/west borders) has the same border fenced as well
+  plots
+    .iter()
+    .map(|p| {
+      let (x, y) = (p.coords.x, p.coords.y);
+      p.fence
+        .iter()
+        .filter(|(_, &fenced)| fenced)
+        .filter(|(border, _)| match border {
+          Border::N | Border::S => y == 0 || !has_fence(x, y - 1, border),
+          Border::E | Border::W => x == 0 || !has_fence(x - 1, y, border),
+        })
+        .count()
+    })
+    .sum()
+}
+
+fn calculate_price_by_sides_count(regions: &HashMap<usize, Vec<&Plot>>) -> usize {
   regions
     .into_iter()
-    // .inspect(|r| println!("\nRegion: {:?}", r))
-    // .inspect(|(_r, plots)| plots.into_iter().for_each(|&p| println!("Fence: {:?}", p.fence)))
-    .map(|(_region, plots)| {
-      (
-        // region,
-        //plots.iter().map(|p| p.).collect::<Vec<_>>(),
-        plots.len(),
-        plots
-          .iter()
-          .map(|p| p.fence.values().filter(|&&v| v).count())
-          // .map(|p| &p.fence)
-          // .map(|fence| fence.values().filter(|&&v| v).count())
-          // .collect::<Vec<_>>(),
-          .sum::<usize>(),
-      )
-    })
-    .map(|(area, perimeter)| area * perimeter)
+    .map(|(_region, plots)| (plots.len(), count_region_sides(plots)))
+    .map(|(area, sides)| area * sides)
     .sum()
 }
 
+fn render_regions(garden: &Garden) -> Vec<Vec<(usize, char)>> {
+  garden
+    .plots
+    .iter()
+    .map(|plots| plots.iter().map(|p| (p.area.unwrap(), p.plant)).collect())
+    .collect()
+}
+
 fn parse_input(input: &str) -> Garden {
   Garden::new(
     input
@@ -215,14 +260,79 @@ fn parse_input(input: &str) -> Garden {
   )
 }
 
+pub fn dump_state(input: &str, output: &render::Output) -> io::Result<()> {
+  let mut garden = parse_input(input);
+  calc_fences_and_areas_dsu(&mut garden);
+  let grid = render_regions(&garden);
+  render::dump(&grid, &render::Palette::new(), |&(_, plant)| plant, output)
+}
+
 pub fn part1(input: &str) -> usize {
   let mut garden = parse_input(input);
   calc_fences_and_areas(&mut garden);
   let regions = get_regions(&garden);
   let price = calculate_price_by_perimeter(&regions);
   price
+}
+
+pub fn part2(input: &str) -> usize {
+  let mut garden = parse_input(input);
+  calc_fences_and_areas(&mut garden);
+  let regions = get_regions(&garden);
+  let price = calculate_price_by_sides_count(&regions);
+  price
+}
 
-  // println!("Price: {:?}", price);
+#[cfg(test)]
+mod tests {
+  use super::*;
 
-  // input.len()
+  const EXAMPLE_1: &str = "AAAA\nBBCD\nBBCC\nEEEC\n";
+  const EXAMPLE_2: &str = "OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO\n";
+  const EXAMPLE_3: &str = "RRRRIICCFF\nRRRRIICCCF\nVVRRRCCFFF\nVVRCCCJFFF\nVVVVCJJCFE\n\
+                           VVIVCCJJEE\nVVIIICJJEE\nMIIIIIJJEE\nMIIISIJEEE\nMMMISSJEEE\n";
+
+  fn price_by_perimeter_dsu(input: &str) -> usize {
+    let mut garden = parse_input(input);
+    calc_fences_and_areas_dsu(&mut garden);
+    let regions = get_regions(&garden);
+    calculate_price_by_perimeter(&regions)
+  }
+
+  #[test]
+  fn test_part1() {
+    assert_eq!(part1(EXAMPLE_1), 140);
+    assert_eq!(part1(EXAMPLE_2), 772);
+    assert_eq!(part1(EXAMPLE_3), 1930);
+  }
+
+  #[test]
+  fn test_part1_dsu() {
+    assert_eq!(price_by_perimeter_dsu(EXAMPLE_1), 140);
+    assert_eq!(price_by_perimeter_dsu(EXAMPLE_2), 772);
+    assert_eq!(price_by_perimeter_dsu(EXAMPLE_3), 1930);
+  }
+
+  #[test]
+  fn test_part1_dsu_many_regions() {
+    // ## a 300x300 checkerboard: 90000 single-plot regions, more than a u16 region id can hold
+    let input: String = (0..300)
+      .map(|x| {
+        (0..300)
+          .map(|y| ['A', 'B'][(x + y) % 2])
+          .chain(['\n'])
+          .collect::<String>()
+      })
+      .collect();
+    assert_eq!(price_by_perimeter_dsu(&input), 90000 * 4);
+  }
+
+  #[test]
+  fn test_part2() {
+    assert_eq!(part2(EXAMPLE_1), 80);
+    assert_eq!(part2(EXAMPLE_2), 436);
+    assert_eq!(part2(EXAMPLE_3), 1206);
+    assert_eq!(part2("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE\n"), 236);
+    assert_eq!(part2("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA\n"), 368);
+  }
 }
@@ -0,0 +1,54 @@
+use super::*;
+use crate::util::io::{bench_spacer, Env};
+
+#[derive(Debug)]
+enum BenchImpl {
+  Part1FloodFill,
+  Part1Dsu,
+  Part2FloodFill,
+  Part2Dsu,
+}
+
+fn run_benchmark(input: &str, fn_impl: BenchImpl) {
+  let now = std::time::Instant::now();
+  let result = match fn_impl {
+    BenchImpl::Part1FloodFill => {
+      let mut garden = parse_input(input);
+      calc_fences_and_areas(&mut garden);
+      let regions = get_regions(&garden);
+      let result = calculate_price_by_perimeter(&regions);
+      result
+    }
+    BenchImpl::Part1Dsu => {
+      let mut garden = parse_input(input);
+      calc_fences_and_areas_dsu(&mut garden);
+      let regions = get_regions(&garden);
+      let result = calculate_price_by_perimeter(&regions);
+      result
+    }
+    BenchImpl::Part2FloodFill => {
+      let mut garden = parse_input(input);
+      calc_fences_and_areas(&mut garden);
+      let regions = get_regions(&garden);
+      let result = calculate_price_by_sides_count(&regions);
+      result
+    }
+    BenchImpl::Part2Dsu => {
+      let mut garden = parse_input(input);
+      calc_fences_and_areas_dsu(&mut garden);
+      let regions = get_regions(&garden);
+      let result = calculate_price_by_sides_count(&regions);
+      result
+    }
+  };
+  let elapsed = now.elapsed();
+  println!("[{fn_impl:#?}] Elapsed {elapsed:.2?}; Result: {result}");
+}
+
+pub fn run(input: &str, env: Env) {
+  bench_spacer(env);
+  run_benchmark(input, BenchImpl::Part1FloodFill);
+  run_benchmark(input, BenchImpl::Part1Dsu);
+  run_benchmark(input, BenchImpl::Part2FloodFill);
+  run_benchmark(input, BenchImpl::Part2Dsu);
+}
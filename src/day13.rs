@@ -1,7 +1,11 @@
 pub mod benchmarks;
+#[cfg(feature = "bigint")]
+pub mod bigint;
 
 use regex::Regex;
 
+use crate::util::checked::{self, ArithmeticError, CheckedOps};
+
 #[derive(Debug)]
 struct EquationsParams {
   ax: i64,
@@ -13,31 +17,40 @@ struct EquationsParams {
 }
 
 impl EquationsParams {
-  fn p_a(&self) -> i64 {
-    self.py * self.ay - self.px * self.by
+  fn p_a(&self) -> Result<i64, ArithmeticError> {
+    self.py.try_mul(self.ay)?.try_sub(self.px.try_mul(self.by)?)
   }
-  fn p_b(&self) -> i64 {
-    self.px * self.bx - self.py * self.ax
+  fn p_b(&self) -> Result<i64, ArithmeticError> {
+    self.px.try_mul(self.bx)?.try_sub(self.py.try_mul(self.ax)?)
   }
-  fn q(&self) -> i64 {
-    self.bx * self.ay - self.ax * self.by
+  fn q(&self) -> Result<i64, ArithmeticError> {
+    self.bx.try_mul(self.ay)?.try_sub(self.ax.try_mul(self.by)?)
   }
-  fn a(&self) -> i64 {
-    self.p_a() / self.q()
+  fn a(&self) -> Result<i64, ArithmeticError> {
+    self.p_a()?.try_div(self.q()?)
   }
-  fn b(&self) -> i64 {
-    self.p_b() / self.q()
+  fn b(&self) -> Result<i64, ArithmeticError> {
+    self.p_b()?.try_div(self.q()?)
   }
-  fn has_winning_combination(&self) -> bool {
-    let (p_a, p_b, q) = (self.p_a(), self.p_b(), self.q());
-    p_a % q == 0 && p_b % q == 0
+  fn has_winning_combination(&self) -> Result<bool, ArithmeticError> {
+    let (p_a, p_b, q) = (self.p_a()?, self.p_b()?, self.q()?);
+    // ## collinear buttons: either no or infinitely many solutions, the puzzle inputs have none of those
+    if q == 0 {
+      return Ok(false);
+    }
+    if p_a.try_rem(q)? != 0 || p_b.try_rem(q)? != 0 {
+      return Ok(false);
+    }
+    // ## buttons can't be pressed a negative number of times
+    let (a, b) = self.winning_combination()?;
+    Ok(a >= 0 && b >= 0)
   }
-  fn winning_combination(&self) -> (i64, i64) {
-    (self.a(), self.b())
+  fn winning_combination(&self) -> Result<(i64, i64), ArithmeticError> {
+    Ok((self.a()?, self.b()?))
   }
-  fn calculate_winning_play_cost(&self) -> usize {
-    let (a, b) = self.winning_combination();
-    (a * 3 + b * 1).try_into().unwrap()
+  fn calculate_winning_play_cost(&self) -> Result<usize, ArithmeticError> {
+    let (a, b) = self.winning_combination()?;
+    checked::try_convert(a.try_mul(3)?.try_add(b.try_mul(1)?)?)
   }
 }
 
@@ -45,30 +58,35 @@ fn calc_winning_plays_cost(
   claw_machines_definitions: Vec<String>,
   winning_coords_add: i64,
   capture_regex: &Regex,
-) -> usize {
+) -> Result<usize, ArithmeticError> {
   claw_machines_definitions
     .into_iter()
     .map(|claw_machine_input| {
       let capture = capture_regex.captures(&claw_machine_input).unwrap();
-      EquationsParams {
-        ax: capture.get(1).unwrap().as_str().parse::<i64>().unwrap(),
-        bx: capture.get(2).unwrap().as_str().parse::<i64>().unwrap(),
-        ay: capture.get(3).unwrap().as_str().parse::<i64>().unwrap(),
-        by: capture.get(4).unwrap().as_str().parse::<i64>().unwrap(),
-        px: capture.get(5).unwrap().as_str().parse::<i64>().unwrap() + winning_coords_add,
-        py: capture.get(6).unwrap().as_str().parse::<i64>().unwrap() + winning_coords_add,
+      let num = |idx: usize| checked::try_parse::<i64>(capture.get(idx).unwrap().as_str());
+      Ok(EquationsParams {
+        ax: num(1)?,
+        bx: num(2)?,
+        ay: num(3)?,
+        by: num(4)?,
+        px: num(5)?.try_add(winning_coords_add)?,
+        py: num(6)?.try_add(winning_coords_add)?,
+      })
+    })
+    .try_fold(0_usize, |acc, claw_machine: Result<EquationsParams, ArithmeticError>| {
+      let cm = claw_machine?;
+      match cm.has_winning_combination()? {
+        true => acc.try_add(cm.calculate_winning_play_cost()?),
+        false => Ok(acc),
       }
     })
-    .filter(|claw_machine| claw_machine.has_winning_combination())
-    .map(|cm| cm.calculate_winning_play_cost())
-    .sum()
 }
 
 fn parse_input(input: &str) -> Vec<String> {
   input.trim().split("\n\n").map(|s| s.to_string()).collect()
 }
 
-pub fn part1(input: &str) -> usize {
+pub fn part1(input: &str) -> Result<usize, ArithmeticError> {
   let winning_coords_add: i64 = 0;
   let matching_expr =
     Regex::new(r"Button A: X\+(\d+), Y\+(\d+)\nButton B: X\+(\d+), Y\+(\d+)\nPrize: X=(\d+), Y=(\d+)").unwrap();
@@ -77,7 +95,7 @@ pub fn part1(input: &str) -> usize {
   calc_winning_plays_cost(claw_machines_definitions, winning_coords_add, &matching_expr)
 }
 
-pub fn part2(input: &str) -> usize {
+pub fn part2(input: &str) -> Result<usize, ArithmeticError> {
   let winning_coords_add: i64 = 10000000000000;
   let matching_expr =
     Regex::new(r"Button A: X\+(\d+), Y\+(\d+)\nButton B: X\+(\d+), Y\+(\d+)\nPrize: X=(\d+), Y=(\d+)").unwrap();
@@ -85,3 +103,49 @@ pub fn part2(input: &str) -> usize {
   let claw_machines_definitions = parse_input(input);
   calc_winning_plays_cost(claw_machines_definitions, winning_coords_add, &matching_expr)
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\n\
+                         Button A: X+26, Y+66\nButton B: X+67, Y+21\nPrize: X=12748, Y=12176\n\n\
+                         Button A: X+17, Y+86\nButton B: X+84, Y+37\nPrize: X=7870, Y=6450\n\n\
+                         Button A: X+69, Y+23\nButton B: X+27, Y+71\nPrize: X=18641, Y=10279\n";
+
+  #[test]
+  fn test_example() {
+    assert_eq!(part1(EXAMPLE), Ok(480));
+    assert_eq!(part2(EXAMPLE), Ok(875318608908));
+  }
+
+  #[test]
+  fn test_overflow_is_reported() {
+    let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=9223372036854775807, Y=5400\n";
+    assert!(matches!(part2(input), Err(ArithmeticError::Overflow(_))));
+  }
+
+  #[test]
+  fn test_collinear_buttons() {
+    let input = "Button A: X+2, Y+4\nButton B: X+1, Y+2\nPrize: X=10, Y=20\n\n\
+                 Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n";
+    assert_eq!(part1(input), Ok(280));
+    #[cfg(feature = "bigint")]
+    assert_eq!(bigint::part1(input), num_bigint::BigInt::from(280));
+  }
+
+  #[test]
+  fn test_negative_presses() {
+    let input = "Button A: X+2, Y+1\nButton B: X+1, Y+2\nPrize: X=0, Y=3\n\n\
+                 Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n";
+    assert_eq!(part1(input), Ok(280));
+    #[cfg(feature = "bigint")]
+    assert_eq!(bigint::part1(input), num_bigint::BigInt::from(280));
+  }
+
+  #[test]
+  fn test_out_of_range_number_is_reported() {
+    let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=99999999999999999999, Y=5400\n";
+    assert!(matches!(part1(input), Err(ArithmeticError::Overflow(_))));
+  }
+}
@@ -12,11 +12,11 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
   let result = match fn_impl {
     BenchImpl::Part1Std => {
       parse_input(input);
-      let result = part1(input);
+      let result = part1(input).unwrap();
       result
     }
     BenchImpl::Part2Std => {
-      let result = part2(input);
+      let result = part2(input).unwrap();
       result
     }
   };
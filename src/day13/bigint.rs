@@ -0,0 +1,83 @@
+use num_bigint::BigInt;
+use regex::Regex;
+
+// Arbitrary-precision variant for scaled-up stress inputs, where prize coordinates overflow `i64`
+#[derive(Debug)]
+struct EquationsParams {
+  ax: BigInt,
+  ay: BigInt,
+  px: BigInt,
+  bx: BigInt,
+  by: BigInt,
+  py: BigInt,
+}
+
+impl EquationsParams {
+  fn p_a(&self) -> BigInt {
+    &self.py * &self.ay - &self.px * &self.by
+  }
+  fn p_b(&self) -> BigInt {
+    &self.px * &self.bx - &self.py * &self.ax
+  }
+  fn q(&self) -> BigInt {
+    &self.bx * &self.ay - &self.ax * &self.by
+  }
+  fn winning_play_cost(&self) -> Option<BigInt> {
+    let (p_a, p_b, q) = (self.p_a(), self.p_b(), self.q());
+    let zero = BigInt::from(0);
+    if q == zero || &p_a % &q != zero || &p_b % &q != zero {
+      return None;
+    }
+    let (a, b) = (p_a / &q, p_b / &q);
+    if a < zero || b < zero {
+      return None;
+    }
+    Some(a * 3 + b)
+  }
+}
+
+fn calc_winning_plays_cost(input: &str, winning_coords_add: &BigInt) -> BigInt {
+  let capture_regex =
+    Regex::new(r"Button A: X\+(\d+), Y\+(\d+)\nButton B: X\+(\d+), Y\+(\d+)\nPrize: X=(\d+), Y=(\d+)").unwrap();
+  let num = |capture: &regex::Captures, idx: usize| capture.get(idx).unwrap().as_str().parse::<BigInt>().unwrap();
+
+  input
+    .trim()
+    .split("\n\n")
+    .map(|claw_machine_input| {
+      let capture = capture_regex.captures(claw_machine_input).unwrap();
+      EquationsParams {
+        ax: num(&capture, 1),
+        bx: num(&capture, 2),
+        ay: num(&capture, 3),
+        by: num(&capture, 4),
+        px: num(&capture, 5) + winning_coords_add,
+        py: num(&capture, 6) + winning_coords_add,
+      }
+    })
+    .filter_map(|cm| cm.winning_play_cost())
+    .sum()
+}
+
+pub fn part1(input: &str) -> BigInt {
+  calc_winning_plays_cost(input, &BigInt::from(0))
+}
+
+pub fn part2(input: &str) -> BigInt {
+  calc_winning_plays_cost(input, &BigInt::from(10000000000000_i64))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_beyond_i64() {
+    let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=9223372036854775807, Y=5400\n";
+    assert!(super::super::part2(input).is_err());
+    assert_eq!(part2(input), BigInt::from(0));
+
+    let input = "Button A: X+2, Y+1\nButton B: X+1, Y+2\nPrize: X=30000000000000000000, Y=30000000000000000000\n";
+    assert_eq!(part1(input), "40000000000000000000".parse::<BigInt>().unwrap());
+  }
+}
@@ -3,6 +3,10 @@ pub mod benchmarks;
 use regex::Regex;
 use statistical;
 use std::collections::HashMap;
+use std::io;
+
+use crate::util::animation::{self, Recorder};
+use crate::util::render::{self, Rgb};
 
 #[derive(Debug, Eq, Hash, PartialEq, Copy, Clone)]
 pub struct Matrix {
@@ -25,13 +29,7 @@ impl Robot {
   }
 }
 
-fn calc_positions_after_n_moves(
-  robots_movements: &Vec<String>,
-  grid_size: Matrix,
-  moves_count: usize,
-) -> HashMap<Matrix, usize> {
-  let mut robo_map: HashMap<Matrix, usize> = HashMap::new();
-
+fn parse_robots(robots_movements: &Vec<String>, grid_size: Matrix) -> Vec<Robot> {
   let capture_regex = Regex::new(r"p=(-?\d+),(-?\d+) v=(-?\d+),(-?\d+)").unwrap();
   robots_movements
     .into_iter()
@@ -48,21 +46,31 @@ fn calc_positions_after_n_moves(
         },
       }
     })
-    .map(|mut r| {
-      r.make_n_moves(moves_count, &grid_size);
-      r.position
-    })
-    .for_each(|pos| {
-      if let Some(count) = robo_map.get_mut(&pos) {
-        *count += 1;
-      } else {
-        robo_map.insert(pos, 1);
-      }
-    });
+    .collect()
+}
 
+fn map_robots(robots: &Vec<Robot>) -> HashMap<Matrix, usize> {
+  let mut robo_map: HashMap<Matrix, usize> = HashMap::new();
+  robots.iter().for_each(|r| {
+    if let Some(count) = robo_map.get_mut(&r.position) {
+      *count += 1;
+    } else {
+      robo_map.insert(r.position, 1);
+    }
+  });
   robo_map
 }
 
+fn calc_positions_after_n_moves(
+  robots_movements: &Vec<String>,
+  grid_size: Matrix,
+  moves_count: usize,
+) -> HashMap<Matrix, usize> {
+  let mut robots = parse_robots(robots_movements, grid_size);
+  robots.iter_mut().for_each(|r| r.make_n_moves(moves_count, &grid_size));
+  map_robots(&robots)
+}
+
 fn calc_safety_factor(robo_map: HashMap<Matrix, usize>, grid_size: Matrix) -> usize {
   let (mid_x, mid_y) = (grid_size.x / 2, grid_size.y / 2);
   let mut quadrants = HashMap::from([(1, 0), (2, 0), (3, 0), (4, 0)]);
@@ -97,10 +105,60 @@ fn calc_variance(robo_map: HashMap<Matrix, usize>, mean_x: i64) -> f64 {
   statistical::population_variance(&robots_x_axes, Some(mean_x as f64))
 }
 
+fn render_robo_map(robo_map: &HashMap<Matrix, usize>, grid_size: Matrix) -> Vec<Vec<usize>> {
+  let mut grid = vec![vec![0; grid_size.x as usize]; grid_size.y as usize];
+  robo_map
+    .iter()
+    .for_each(|(pos, &count)| grid[pos.y as usize][pos.x as usize] = count);
+  grid
+}
+
+fn robots_palette() -> render::Palette<usize> {
+  render::Palette::new()
+    .with(0, Rgb(16, 16, 16))
+    .with_fallback(Rgb(0, 200, 64))
+}
+
+fn robot_glyph(count: &usize) -> char {
+  match count {
+    0 => '.',
+    1..=9 => char::from_digit(*count as u32, 10).unwrap(),
+    _ => '+',
+  }
+}
+
 fn parse_input(input: &str) -> Vec<String> {
   input.trim().lines().map(|s| s.to_string()).collect()
 }
 
+pub fn dump_state(input: &str, grid_size: Matrix, moves_count: usize, output: &render::Output) -> io::Result<()> {
+  let robots_movements = parse_input(input);
+  let robo_map = calc_positions_after_n_moves(&robots_movements, grid_size, moves_count);
+  let grid = render_robo_map(&robo_map, grid_size);
+  render::dump(&grid, &robots_palette(), robot_glyph, output)
+}
+
+pub fn animate(
+  input: &str,
+  grid_size: Matrix,
+  moves_count: usize,
+  every_nth: usize,
+  target: &animation::Target,
+) -> io::Result<()> {
+  let robots_movements = parse_input(input);
+  let mut robots = parse_robots(&robots_movements, grid_size);
+  let palette = robots_palette();
+  let mut recorder = Recorder::for_target(every_nth, &palette, target)?;
+
+  for step in 0..moves_count {
+    recorder.capture(step, || render_robo_map(&map_robots(&robots), grid_size));
+    robots.iter_mut().for_each(|r| r.make_n_moves(1, &grid_size));
+  }
+  recorder.capture_last(moves_count, || render_robo_map(&map_robots(&robots), grid_size));
+
+  recorder.emit(&palette, robot_glyph, target)
+}
+
 pub fn part1(input: &str) -> usize {
   let moves_count = 100;
   let grid_size = Matrix { x: 101, y: 103 };
@@ -1,4 +1,8 @@
 use std::collections::HashMap;
+use std::io;
+
+use crate::util::animation::{self, Recorder};
+use crate::util::render::{self, Rgb};
 
 pub mod benchmarks;
 
@@ -36,22 +40,45 @@ struct Map {
   map_fields: Vec<char>,
 }
 
-fn parse_input(input: &str) -> (Map, Vec<char>) {
-  let (map_input, robot_moves_input) = input.split_once("\n\n").unwrap();
-  let map_input_rows_iter = map_input.lines();
-  let robot_moves = robot_moves_input.replace("\n", "").chars().collect::<Vec<char>>();
+fn parse_map(map_input: &str) -> Map {
+  let map_input_rows_iter = map_input.lines().filter(|l| !l.is_empty());
 
+  // ## `x` counts the rows and `y` the columns, so the fields are indexed as `x * map_size.y + y`
   let map_size = Coords {
-    x: map_input_rows_iter.clone().nth(1).unwrap().len(),
-    y: map_input_rows_iter.clone().count(),
+    x: map_input_rows_iter.clone().count(),
+    y: map_input_rows_iter.clone().nth(1).unwrap().len(),
   };
 
-  let map_fields = map_input_rows_iter
-    .filter(|l| !l.is_empty())
-    .flat_map(|l| l.chars())
-    .collect::<Vec<char>>();
+  let map_fields = map_input_rows_iter.flat_map(|l| l.chars()).collect::<Vec<char>>();
 
-  (Map { map_size, map_fields }, robot_moves)
+  Map { map_size, map_fields }
+}
+
+fn parse_input(input: &str) -> (Map, Vec<char>) {
+  let (map_input, robot_moves_input) = input.split_once("\n\n").unwrap();
+  let robot_moves = robot_moves_input.replace("\n", "").chars().collect::<Vec<char>>();
+  (parse_map(map_input), robot_moves)
+}
+
+// Every tile gets twice as wide: `#` => `##`, `O` => `[]`, `.` => `..`, `@` => `@.`
+fn scale_up_map_input(map_input: &str) -> String {
+  map_input
+    .chars()
+    .map(|c| match c {
+      '#' => "##",
+      'O' => "[]",
+      '.' => "..",
+      '@' => "@.",
+      '\n' => "\n",
+      _ => panic!("CRITICAL: unexpected map field {c:?}"),
+    })
+    .collect()
+}
+
+fn parse_input_wide(input: &str) -> (Map, Vec<char>) {
+  let (map_input, robot_moves_input) = input.split_once("\n\n").unwrap();
+  let robot_moves = robot_moves_input.replace("\n", "").chars().collect::<Vec<char>>();
+  (parse_map(&scale_up_map_input(map_input)), robot_moves)
 }
 
 fn get_coords_from_idx(map_size: &Coords, idx: usize) -> Coords {
@@ -61,7 +88,7 @@ fn get_coords_from_idx(map_size: &Coords, idx: usize) -> Coords {
 }
 
 fn find_next_field_idx(map_size: &Coords, robot_pos_idx: &usize, diff: &Diff) -> usize {
-  let next_field_idx_diff = diff.x * map_size.x as i64 + diff.y;
+  let next_field_idx_diff = diff.x * map_size.y as i64 + diff.y;
   let next_field_idx = *robot_pos_idx as i64 + next_field_idx_diff;
   next_field_idx as usize
 }
@@ -72,7 +99,7 @@ fn find_free_spot(map: &Map, robot_pos_idx: &usize, diff: &Diff) -> Option<usize
 
   loop {
     let (gap_diff_x, gap_diff_y) = (gap * diff.x, gap * diff.y);
-    let map_diff = gap_diff_x * map.map_size.x as i64 + gap_diff_y;
+    let map_diff = gap_diff_x * map.map_size.y as i64 + gap_diff_y;
     let free_space_idx = (*robot_pos_idx as i64 + map_diff) as usize;
     match map_fields[free_space_idx] {
       '.' => return Some(free_space_idx),
@@ -97,26 +124,93 @@ fn make_a_move(map: &mut Map, diff: &Diff) {
   }
 }
 
+// Collects all the fields (robot included) a vertical push moves, following `[]` box halves into
+// whole trees of boxes; `None` if any of them would hit a wall
+fn collect_vertical_push(map: &Map, robot_pos_idx: usize, diff: &Diff) -> Option<Vec<usize>> {
+  let mut to_move: Vec<usize> = vec![robot_pos_idx];
+  let mut idx = 0;
+
+  while idx < to_move.len() {
+    let next_field_idx = find_next_field_idx(&map.map_size, &to_move[idx], diff);
+    idx += 1;
+
+    let box_halves = match map.map_fields[next_field_idx] {
+      '#' => return None,
+      '[' => vec![next_field_idx, next_field_idx + 1],
+      ']' => vec![next_field_idx - 1, next_field_idx],
+      _ => vec![],
+    };
+    box_halves.into_iter().for_each(|half| {
+      if !to_move.contains(&half) {
+        to_move.push(half);
+      }
+    });
+  }
+  Some(to_move)
+}
+
+fn make_a_wide_move(map: &mut Map, diff: &Diff) {
+  let robot_pos_idx = map.map_fields.iter().position(|&c| c == '@').unwrap();
+
+  if diff.x == 0 {
+    // ## horizontal pushes shift the whole row segment between the robot and the free spot
+    if let Some(free_space_idx) = find_free_spot(map, &robot_pos_idx, diff) {
+      match diff.y > 0 {
+        true => map.map_fields[robot_pos_idx..=free_space_idx].rotate_right(1),
+        false => map.map_fields[free_space_idx..=robot_pos_idx].rotate_left(1),
+      }
+    }
+    return;
+  }
+
+  // ## vertical pushes move the whole tree of boxes at once, or nothing at all
+  if let Some(to_move) = collect_vertical_push(map, robot_pos_idx, diff) {
+    let moved: Vec<(usize, char)> = to_move.into_iter().map(|idx| (idx, map.map_fields[idx])).collect();
+    moved.iter().for_each(|&(idx, _)| map.map_fields[idx] = '.');
+    moved.iter().for_each(|&(idx, field)| {
+      let next_field_idx = find_next_field_idx(&map.map_size, &idx, diff);
+      map.map_fields[next_field_idx] = field;
+    });
+  }
+}
+
 fn simulate_robot_movement(map: &mut Map, robot_moves: &Vec<char>) {
+  simulate_robot_movement_recorded(map, robot_moves, make_a_move, &mut Recorder::disabled());
+}
+
+fn simulate_wide_robot_movement(map: &mut Map, robot_moves: &Vec<char>) {
+  simulate_robot_movement_recorded(map, robot_moves, make_a_wide_move, &mut Recorder::disabled());
+}
+
+fn simulate_robot_movement_recorded(
+  map: &mut Map,
+  robot_moves: &Vec<char>,
+  make_move: fn(&mut Map, &Diff),
+  recorder: &mut Recorder<char>,
+) {
   let move_directions = get_move_directions();
-  let mut robot_moves_iter = robot_moves.into_iter();
-  while let Some(&mv) = robot_moves_iter.next() {
+  let mut robot_moves_iter = robot_moves.into_iter().enumerate();
+  while let Some((step, &mv)) = robot_moves_iter.next() {
+    recorder.capture(step, || render_map(map));
     match mv {
-      '^' => make_a_move(map, &move_directions[&Direction::N]),
-      '>' => make_a_move(map, &move_directions[&Direction::E]),
-      'v' => make_a_move(map, &move_directions[&Direction::S]),
-      '<' => make_a_move(map, &move_directions[&Direction::W]),
-      _ => todo!(),
+      '^' => make_move(map, &move_directions[&Direction::N]),
+      '>' => make_move(map, &move_directions[&Direction::E]),
+      'v' => make_move(map, &move_directions[&Direction::S]),
+      '<' => make_move(map, &move_directions[&Direction::W]),
+      c if c.is_whitespace() => {}
+      _ => panic!("CRITICAL: unexpected robot move {mv:?}"),
     }
   }
+  recorder.capture_last(robot_moves.len(), || render_map(map));
 }
 
-fn sum_gps_coordinates(map: &Map) -> usize {
+// GPS coordinates of boxes, measured from the top-left box field (`O`, or `[` for wide boxes)
+fn sum_gps_coordinates(map: &Map, box_marker: char) -> usize {
   map
     .map_fields
     .iter()
     .enumerate()
-    .filter(|(_, field)| **field == 'O')
+    .filter(|(_, field)| **field == box_marker)
     .map(|(idx, _)| {
       let coords = get_coords_from_idx(&map.map_size, idx);
       coords.x * 100 + coords.y
@@ -124,15 +218,44 @@ fn sum_gps_coordinates(map: &Map) -> usize {
     .sum()
 }
 
+fn render_map(map: &Map) -> Vec<Vec<char>> {
+  map.map_fields.chunks(map.map_size.y).map(|row| row.to_vec()).collect()
+}
+
+fn warehouse_palette() -> render::Palette<char> {
+  render::char_map_palette()
+    .with('@', Rgb(255, 64, 64))
+    .with('O', Rgb(160, 100, 40))
+    .with('[', Rgb(160, 100, 40))
+    .with(']', Rgb(160, 100, 40))
+}
+
+pub fn dump_state(input: &str, output: &render::Output) -> io::Result<()> {
+  let (mut map, robot_moves) = parse_input(input);
+  simulate_robot_movement(&mut map, &robot_moves);
+  render::dump_chars(&render_map(&map), &warehouse_palette(), output)
+}
+
+pub fn animate(input: &str, every_nth: usize, target: &animation::Target) -> io::Result<()> {
+  let (mut map, robot_moves) = parse_input(input);
+  let palette = warehouse_palette();
+  let mut recorder = Recorder::for_target(every_nth, &palette, target)?;
+  simulate_robot_movement_recorded(&mut map, &robot_moves, make_a_move, &mut recorder);
+  recorder.emit(&palette, |&c| c, target)
+}
+
 pub fn part1(input: &str) -> usize {
   let (mut map, robot_moves) = parse_input(input);
   simulate_robot_movement(&mut map, &robot_moves);
-  let result = sum_gps_coordinates(&map);
+  let result = sum_gps_coordinates(&map, 'O');
   result
 }
 
 pub fn part2(input: &str) -> usize {
-  input.len()
+  let (mut map, robot_moves) = parse_input_wide(input);
+  simulate_wide_robot_movement(&mut map, &robot_moves);
+  let result = sum_gps_coordinates(&map, '[');
+  result
 }
 
 #[cfg(test)]
@@ -155,4 +278,46 @@ mod tests {
     let result = get_coords_from_idx(&map_size, idx);
     assert_eq!(result, Coords { x: 1, y: 4 });
   }
+
+  const SMALL_EXAMPLE: &str = "########\n#..O.O.#\n##@.O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n\n\
+                               <^^>>>vv<v>>v<<\n";
+
+  const LARGE_EXAMPLE: &str = "##########\n#..O..O.O#\n#......O.#\n#.OO..O.O#\n#..O@..O.#\n#O#..O...#\n\
+                               #O..O..O.#\n#.OO.O.OO#\n#....O...#\n##########\n\n\
+                               <vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^\n\
+                               vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v\n\
+                               ><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<\n\
+                               <<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^\n\
+                               ^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><\n\
+                               ^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^\n\
+                               >^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^\n\
+                               <><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>\n\
+                               ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>\n\
+                               v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^\n";
+
+  #[test]
+  fn test_part1() {
+    assert_eq!(part1(SMALL_EXAMPLE), 2028);
+    assert_eq!(part1(LARGE_EXAMPLE), 10092);
+  }
+
+  #[test]
+  fn test_part2() {
+    assert_eq!(part2(LARGE_EXAMPLE), 9021);
+  }
+
+  #[test]
+  fn test_wide_vertical_push_moves_box_trees_atomically() {
+    let input = "#######\n#...#.#\n#.....#\n#..OO@#\n#..O..#\n#.....#\n#######\n\n<vv<<^^<<^^\n";
+    let (mut map, robot_moves) = parse_input_wide(input);
+    simulate_wide_robot_movement(&mut map, &robot_moves);
+
+    let expected = "##############\n##...[].##..##\n##...@.[]...##\n##....[]....##\n\
+                    ##..........##\n##..........##\n##############";
+    let rendered = render_map(&map)
+      .into_iter()
+      .map(|row| row.into_iter().collect::<String>())
+      .collect::<Vec<_>>();
+    assert_eq!(rendered.join("\n"), expected);
+  }
 }
@@ -26,5 +26,5 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
 pub fn run(input: &str, env: Env) {
   bench_spacer(env);
   run_benchmark(input, BenchImpl::Part1Std);
-  // run_benchmark(input, BenchImpl::Part2Std);
+  run_benchmark(input, BenchImpl::Part2Std);
 }
@@ -0,0 +1,231 @@
+pub mod benchmarks;
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+const STEP_COST: usize = 1;
+const TURN_COST: usize = 1000;
+
+#[derive(Debug, Eq, Hash, PartialEq, Ord, PartialOrd, Clone, Copy)]
+enum Dir {
+  N,
+  E,
+  S,
+  W,
+}
+
+impl Dir {
+  const ALL: [Dir; 4] = [Dir::N, Dir::E, Dir::S, Dir::W];
+
+  fn diff(&self) -> (isize, isize) {
+    match self {
+      Dir::N => (-1, 0),
+      Dir::E => (0, 1),
+      Dir::S => (1, 0),
+      Dir::W => (0, -1),
+    }
+  }
+
+  fn turns(&self) -> [Dir; 2] {
+    match self {
+      Dir::N | Dir::S => [Dir::E, Dir::W],
+      Dir::E | Dir::W => [Dir::N, Dir::S],
+    }
+  }
+}
+
+#[derive(Debug, Eq, Hash, PartialEq, Ord, PartialOrd, Clone, Copy)]
+struct Pos {
+  i: usize,
+  j: usize,
+}
+
+// ## a reindeer state is where it stands and where it's facing
+#[derive(Debug, Eq, Hash, PartialEq, Ord, PartialOrd, Clone, Copy)]
+struct State {
+  pos: Pos,
+  dir: Dir,
+}
+
+struct Maze {
+  grid: Vec<Vec<char>>,
+  start: Pos,
+  end: Pos,
+}
+
+struct Paths {
+  scores: Vec<Vec<[usize; 4]>>,
+  predecessors: Vec<Vec<[Vec<State>; 4]>>,
+}
+
+impl Paths {
+  fn score(&self, state: &State) -> usize {
+    self.scores[state.pos.i][state.pos.j][state.dir as usize]
+  }
+}
+
+fn find_marker(grid: &Vec<Vec<char>>, marker: char) -> Pos {
+  grid
+    .iter()
+    .enumerate()
+    .find_map(|(i, row)| row.iter().position(|&c| c == marker).map(|j| Pos { i, j }))
+    .unwrap()
+}
+
+fn parse_input(input: &str) -> Maze {
+  let grid: Vec<Vec<char>> = input.trim().lines().map(|l| l.chars().collect()).collect();
+  let start = find_marker(&grid, 'S');
+  let end = find_marker(&grid, 'E');
+  Maze { grid, start, end }
+}
+
+fn get_next_states(maze: &Maze, state: &State) -> Vec<(State, usize)> {
+  let mut next_states: Vec<(State, usize)> = state
+    .dir
+    .turns()
+    .into_iter()
+    .map(|dir| (State { pos: state.pos, dir }, TURN_COST))
+    .collect();
+
+  let (di, dj) = state.dir.diff();
+  let (i, j) = (state.pos.i as isize + di, state.pos.j as isize + dj);
+  // ## the maze is surrounded by walls, so a step never leaves the grid
+  if maze.grid[i as usize][j as usize] != '#' {
+    let pos = Pos {
+      i: i as usize,
+      j: j as usize,
+    };
+    next_states.push((State { pos, dir: state.dir }, STEP_COST));
+  }
+  next_states
+}
+
+// Dijkstra over (position, facing), remembering every predecessor reaching a state with the same best score
+fn find_best_paths(maze: &Maze) -> Paths {
+  let (rows, cols) = (maze.grid.len(), maze.grid[0].len());
+  let mut paths = Paths {
+    scores: vec![vec![[usize::MAX; 4]; cols]; rows],
+    predecessors: vec![vec![[vec![], vec![], vec![], vec![]]; cols]; rows],
+  };
+
+  let start = State {
+    pos: maze.start,
+    dir: Dir::E,
+  };
+  paths.scores[start.pos.i][start.pos.j][start.dir as usize] = 0;
+  let mut queue = BinaryHeap::from([Reverse((0, start))]);
+
+  while let Some(Reverse((score, state))) = queue.pop() {
+    if score > paths.score(&state) {
+      continue;
+    }
+    for (next, cost) in get_next_states(maze, &state) {
+      let next_score = score + cost;
+      let best_score = &mut paths.scores[next.pos.i][next.pos.j][next.dir as usize];
+      let predecessors = &mut paths.predecessors[next.pos.i][next.pos.j][next.dir as usize];
+      if next_score < *best_score {
+        *best_score = next_score;
+        *predecessors = vec![state];
+        queue.push(Reverse((next_score, next)));
+      } else if next_score == *best_score {
+        predecessors.push(state);
+      }
+    }
+  }
+  paths
+}
+
+fn get_best_end_states(maze: &Maze, paths: &Paths) -> (usize, Vec<State>) {
+  let end_states: Vec<State> = Dir::ALL.into_iter().map(|dir| State { pos: maze.end, dir }).collect();
+  let best_score = end_states.iter().map(|s| paths.score(s)).min().unwrap();
+  let best_states = end_states
+    .into_iter()
+    .filter(|s| paths.score(s) == best_score)
+    .collect();
+  (best_score, best_states)
+}
+
+fn count_best_paths_tiles(maze: &Maze, paths: &Paths) -> usize {
+  let (_best_score, end_states) = get_best_end_states(maze, paths);
+  let mut visited: HashSet<State> = HashSet::from_iter(end_states.iter().copied());
+  let mut stack: Vec<State> = end_states;
+
+  while let Some(state) = stack.pop() {
+    paths.predecessors[state.pos.i][state.pos.j][state.dir as usize]
+      .iter()
+      .for_each(|prev| {
+        if visited.insert(*prev) {
+          stack.push(*prev);
+        }
+      });
+  }
+
+  visited.into_iter().map(|s| s.pos).collect::<HashSet<Pos>>().len()
+}
+
+pub fn part1(input: &str) -> usize {
+  let maze = parse_input(input);
+  let paths = find_best_paths(&maze);
+  let (result, _) = get_best_end_states(&maze, &paths);
+  result
+}
+
+pub fn part2(input: &str) -> usize {
+  let maze = parse_input(input);
+  let paths = find_best_paths(&maze);
+  let result = count_best_paths_tiles(&maze, &paths);
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE_1: &str = "###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############
+";
+
+  const EXAMPLE_2: &str = "#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################
+";
+
+  #[test]
+  fn test_part1() {
+    assert_eq!(part1(EXAMPLE_1), 7036);
+    assert_eq!(part1(EXAMPLE_2), 11048);
+  }
+
+  #[test]
+  fn test_part2() {
+    assert_eq!(part2(EXAMPLE_1), 45);
+    assert_eq!(part2(EXAMPLE_2), 64);
+  }
+}
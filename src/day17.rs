@@ -0,0 +1,311 @@
+pub mod benchmarks;
+
+use std::fmt;
+
+use itertools::Itertools;
+use regex::Regex;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Opcode {
+  Adv,
+  Bxl,
+  Bst,
+  Jnz,
+  Bxc,
+  Out,
+  Bdv,
+  Cdv,
+}
+
+#[derive(Debug)]
+enum ParseOpcodeError {
+  BadOpcode,
+}
+
+impl TryFrom<u8> for Opcode {
+  type Error = ParseOpcodeError;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    match value {
+      0 => Ok(Opcode::Adv),
+      1 => Ok(Opcode::Bxl),
+      2 => Ok(Opcode::Bst),
+      3 => Ok(Opcode::Jnz),
+      4 => Ok(Opcode::Bxc),
+      5 => Ok(Opcode::Out),
+      6 => Ok(Opcode::Bdv),
+      7 => Ok(Opcode::Cdv),
+      _ => Err(ParseOpcodeError::BadOpcode),
+    }
+  }
+}
+
+impl Opcode {
+  fn uses_combo_operand(&self) -> bool {
+    matches!(self, Opcode::Adv | Opcode::Bst | Opcode::Out | Opcode::Bdv | Opcode::Cdv)
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum QuineError {
+  NoThreeBitShift,
+  NotFound,
+}
+
+impl fmt::Display for QuineError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      QuineError::NoThreeBitShift => write!(f, "program never shifts A by 3 bits (`adv 3`)"),
+      QuineError::NotFound => write!(f, "no value of A makes the program output itself"),
+    }
+  }
+}
+
+impl std::error::Error for QuineError {}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Computer {
+  a: u64,
+  b: u64,
+  c: u64,
+  ip: usize,
+  output: Vec<u8>,
+}
+
+impl Computer {
+  fn new(a: u64, b: u64, c: u64) -> Self {
+    Self {
+      a,
+      b,
+      c,
+      ip: 0,
+      output: vec![],
+    }
+  }
+
+  fn combo(&self, operand: u8) -> u64 {
+    match operand {
+      0..=3 => operand as u64,
+      4 => self.a,
+      5 => self.b,
+      6 => self.c,
+      _ => panic!("CRITICAL: combo operand {operand} is reserved"),
+    }
+  }
+
+  // A shifted right by a combo operand; shifting by 64 bits or more clears it
+  fn shifted_a(&self, operand: u8) -> u64 {
+    let shift = self.combo(operand);
+    self.a.checked_shr(shift.try_into().unwrap_or(u32::MAX)).unwrap_or(0)
+  }
+
+  // Executes the instruction under the instruction pointer; returns false once the program halts
+  fn step(&mut self, program: &Vec<u8>) -> bool {
+    if self.ip + 1 >= program.len() {
+      return false;
+    }
+
+    let opcode = Opcode::try_from(program[self.ip]).unwrap();
+    let operand = program[self.ip + 1];
+    self.ip += 2;
+
+    match opcode {
+      Opcode::Adv => self.a = self.shifted_a(operand),
+      Opcode::Bxl => self.b ^= operand as u64,
+      Opcode::Bst => self.b = self.combo(operand) % 8,
+      Opcode::Jnz => {
+        if self.a != 0 {
+          self.ip = operand as usize;
+        }
+      }
+      Opcode::Bxc => self.b ^= self.c,
+      Opcode::Out => self.output.push((self.combo(operand) % 8) as u8),
+      Opcode::Bdv => self.b = self.shifted_a(operand),
+      Opcode::Cdv => self.c = self.shifted_a(operand),
+    }
+    true
+  }
+
+  fn run(&mut self, program: &Vec<u8>) -> &Vec<u8> {
+    while self.step(program) {}
+    &self.output
+  }
+}
+
+fn parse_input(input: &str) -> (Computer, Vec<u8>) {
+  let (registers, program) = input.trim().split_once("\n\n").unwrap();
+
+  let capture_regex = Regex::new(r"Register [ABC]: (\d+)").unwrap();
+  let (a, b, c) = capture_regex
+    .captures_iter(registers)
+    .map(|capture| capture.get(1).unwrap().as_str().parse::<u64>().unwrap())
+    .collect_tuple()
+    .unwrap();
+
+  let program = program
+    .trim_start_matches("Program: ")
+    .split(',')
+    .map(|v| v.parse::<u8>().unwrap())
+    .collect();
+
+  (Computer::new(a, b, c), program)
+}
+
+fn format_combo_operand(operand: u8) -> String {
+  match operand {
+    0..=3 => operand.to_string(),
+    4 => String::from("A"),
+    5 => String::from("B"),
+    6 => String::from("C"),
+    _ => String::from("<reserved>"),
+  }
+}
+
+// One line per instruction: `<address>: <mnemonic> <operand>`, combo operands shown as registers
+pub fn disassemble(program: &Vec<u8>) -> Vec<String> {
+  program
+    .chunks(2)
+    .enumerate()
+    .map(|(idx, instruction)| {
+      let address = idx * 2;
+      let (opcode, operand) = match instruction {
+        [opcode, operand] => (Opcode::try_from(*opcode).unwrap(), *operand),
+        _ => panic!("CRITICAL: instruction at {address} has no operand"),
+      };
+      let operand = match opcode.uses_combo_operand() {
+        true => format_combo_operand(operand),
+        false => operand.to_string(),
+      };
+      format!("{address}: {} {operand}", format!("{opcode:?}").to_lowercase())
+    })
+    .collect()
+}
+
+pub fn disassemble_input(input: &str) -> Vec<String> {
+  let (_computer, program) = parse_input(input);
+  disassemble(&program)
+}
+
+fn run_with_a(computer: &Computer, program: &Vec<u8>, a: u64) -> Vec<u8> {
+  let mut computer = Computer { a, ..computer.clone() };
+  computer.run(program).to_vec()
+}
+
+// ## The program is a loop consuming A three bits per iteration, printing one value each time, so the last
+// ## output only depends on the highest 3 bits of A. Going backwards, every matching A is extended by the next 3 bits
+// ## and kept only if the output matches the program's suffix from then on.
+fn find_quine_register_a(computer: &Computer, program: &Vec<u8>) -> Result<u64, QuineError> {
+  if !program.chunks(2).any(|i| i == [0, 3]) {
+    return Err(QuineError::NoThreeBitShift);
+  }
+
+  let mut candidates: Vec<u64> = vec![0];
+  for idx in (0..program.len()).rev() {
+    candidates = candidates
+      .iter()
+      .flat_map(|a| (0..8).map(move |bits| a << 3 | bits))
+      .filter(|&a| run_with_a(computer, program, a) == program[idx..])
+      .collect();
+  }
+  candidates
+    .into_iter()
+    .filter(|&a| a > 0)
+    .min()
+    .ok_or(QuineError::NotFound)
+}
+
+pub fn part1(input: &str) -> String {
+  let (mut computer, program) = parse_input(input);
+  let result = computer.run(&program).iter().join(",");
+  result
+}
+
+pub fn part2(input: &str) -> Result<u64, QuineError> {
+  let (computer, program) = parse_input(input);
+  let result = find_quine_register_a(&computer, &program);
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE_1: &str = "Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+";
+
+  const EXAMPLE_2: &str = "Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0
+";
+
+  #[test]
+  fn test_instructions() {
+    let mut computer = Computer::new(0, 0, 9);
+    computer.run(&vec![2, 6]);
+    assert_eq!(computer.b, 1);
+
+    let mut computer = Computer::new(10, 0, 0);
+    assert_eq!(computer.run(&vec![5, 0, 5, 1, 5, 4]), &vec![0, 1, 2]);
+
+    let mut computer = Computer::new(2024, 0, 0);
+    assert_eq!(computer.run(&vec![0, 1, 5, 4, 3, 0]), &vec![4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
+    assert_eq!(computer.a, 0);
+
+    let mut computer = Computer::new(0, 2024, 43690);
+    computer.run(&vec![4, 0]);
+    assert_eq!(computer.b, 44354);
+  }
+
+  #[test]
+  fn test_wide_shifts() {
+    // ## cdv B, bdv C and adv B with B and C at or beyond the register width
+    let mut computer = Computer::new(u64::MAX, 64, 0);
+    computer.run(&vec![7, 5]);
+    assert_eq!(computer.c, 0);
+
+    let mut computer = Computer::new(u64::MAX, 0, 1 << 40);
+    computer.run(&vec![6, 6]);
+    assert_eq!(computer.b, 0);
+
+    let mut computer = Computer::new(u64::MAX, u64::MAX, 0);
+    computer.run(&vec![0, 5]);
+    assert_eq!(computer.a, 0);
+
+    let mut computer = Computer::new(u64::MAX, 63, 0);
+    computer.run(&vec![0, 5]);
+    assert_eq!(computer.a, 1);
+  }
+
+  #[test]
+  fn test_disassemble() {
+    assert_eq!(disassemble_input(EXAMPLE_1), vec!["0: adv 1", "2: out A", "4: jnz 0"]);
+  }
+
+  #[test]
+  fn test_part1() {
+    assert_eq!(part1(EXAMPLE_1), "4,6,3,5,6,3,5,2,1,0");
+  }
+
+  #[test]
+  fn test_part2() {
+    assert_eq!(part2(EXAMPLE_2), Ok(117440));
+  }
+
+  #[test]
+  fn test_part2_without_three_bit_shift() {
+    assert_eq!(part2(EXAMPLE_1), Err(QuineError::NoThreeBitShift));
+  }
+
+  #[test]
+  fn test_part2_without_quine() {
+    // ## the last iteration shifts A down to 0 and prints 0 ^ 1, never the program's final 0
+    let input = "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 0,3,2,4,1,1,5,5,3,0\n";
+    assert_eq!(part2(input), Err(QuineError::NotFound));
+  }
+}
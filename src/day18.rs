@@ -0,0 +1,214 @@
+pub mod benchmarks;
+
+use std::collections::VecDeque;
+
+use crate::util::dsu::Dsu;
+
+#[derive(Debug, Eq, Hash, PartialEq, Copy, Clone)]
+pub struct Matrix {
+  pub x: usize,
+  pub y: usize,
+}
+
+const DIFFS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+fn parse_input(input: &str) -> Vec<Matrix> {
+  input
+    .trim()
+    .lines()
+    .map(|l| {
+      let (x, y) = l.split_once(',').unwrap();
+      Matrix {
+        x: x.parse::<usize>().unwrap(),
+        y: y.parse::<usize>().unwrap(),
+      }
+    })
+    .collect()
+}
+
+fn get_idx(pos: &Matrix, grid_size: Matrix) -> usize {
+  pos.y * grid_size.x + pos.x
+}
+
+fn get_neighbours(idx: usize, grid_size: Matrix) -> impl Iterator<Item = usize> {
+  let (x, y) = ((idx % grid_size.x) as isize, (idx / grid_size.x) as isize);
+  DIFFS.iter().filter_map(move |(dx, dy)| {
+    let (nx, ny) = (x + dx, y + dy);
+    let is_inside = nx >= 0 && ny >= 0 && nx < grid_size.x as isize && ny < grid_size.y as isize;
+    is_inside.then(|| {
+      get_idx(
+        &Matrix {
+          x: nx as usize,
+          y: ny as usize,
+        },
+        grid_size,
+      )
+    })
+  })
+}
+
+fn get_corrupted(bytes: &Vec<Matrix>, grid_size: Matrix, bytes_count: usize) -> Vec<bool> {
+  let mut corrupted = vec![false; grid_size.x * grid_size.y];
+  bytes
+    .iter()
+    .take(bytes_count)
+    .for_each(|b| corrupted[get_idx(b, grid_size)] = true);
+  corrupted
+}
+
+// BFS from the top-left to the bottom-right corner; None when the exit can't be reached
+fn find_shortest_path(corrupted: &Vec<bool>, grid_size: Matrix) -> Option<usize> {
+  let (start, exit) = (0, grid_size.x * grid_size.y - 1);
+  if corrupted[start] || corrupted[exit] {
+    return None;
+  }
+
+  let mut steps: Vec<Option<usize>> = vec![None; corrupted.len()];
+  steps[start] = Some(0);
+  let mut queue = VecDeque::from([start]);
+
+  while let Some(idx) = queue.pop_front() {
+    let next_steps = steps[idx].unwrap() + 1;
+    if idx == exit {
+      break;
+    }
+    get_neighbours(idx, grid_size).for_each(|n| {
+      if !corrupted[n] && steps[n].is_none() {
+        steps[n] = Some(next_steps);
+        queue.push_back(n);
+      }
+    });
+  }
+  steps[exit]
+}
+
+// ## the exit is reachable after the first `n` bytes for every `n` below the answer, and never again afterwards
+fn find_first_blocking_byte_binary_search(bytes: &Vec<Matrix>, grid_size: Matrix) -> Option<Matrix> {
+  let is_reachable = |n: usize| find_shortest_path(&get_corrupted(bytes, grid_size, n), grid_size).is_some();
+  if is_reachable(bytes.len()) {
+    return None;
+  }
+
+  let (mut low, mut high) = (0, bytes.len());
+  while low < high {
+    let mid = (low + high) / 2;
+    match is_reachable(mid + 1) {
+      true => low = mid + 1,
+      false => high = mid,
+    }
+  }
+  Some(bytes[low])
+}
+
+// ## start from the fully corrupted grid and let the bytes "unfall" in reverse order, joining the freed cell with its
+// ## free neighbours; the byte which finally connects start and exit is the first one that blocked them
+fn find_first_blocking_byte_dsu(bytes: &Vec<Matrix>, grid_size: Matrix) -> Option<Matrix> {
+  let cells_count = grid_size.x * grid_size.y;
+  let (start, exit) = (0, cells_count - 1);
+
+  // ## a cell stays corrupted from the first byte falling onto it
+  let mut fallen_at: Vec<usize> = vec![usize::MAX; cells_count];
+  bytes
+    .iter()
+    .enumerate()
+    .rev()
+    .for_each(|(i, b)| fallen_at[get_idx(b, grid_size)] = i);
+
+  let mut dsu = Dsu::new(cells_count);
+  let join_free_neighbours = |dsu: &mut Dsu, idx: usize, fallen_before: usize| {
+    get_neighbours(idx, grid_size)
+      .filter(|&n| fallen_at[n] >= fallen_before)
+      .for_each(|n| {
+        dsu.union(idx, n);
+      });
+  };
+
+  (0..cells_count)
+    .filter(|&idx| fallen_at[idx] == usize::MAX)
+    .for_each(|idx| join_free_neighbours(&mut dsu, idx, usize::MAX));
+  if dsu.connected(start, exit) {
+    return None;
+  }
+
+  bytes.iter().enumerate().rev().find_map(|(i, b)| {
+    let idx = get_idx(b, grid_size);
+    if fallen_at[idx] != i {
+      return None;
+    }
+    join_free_neighbours(&mut dsu, idx, i);
+    let is_free_path = dsu.connected(start, exit) && fallen_at[start] >= i && fallen_at[exit] >= i;
+    is_free_path.then_some(*b)
+  })
+}
+
+fn format_byte(byte: &Matrix) -> String {
+  format!("{},{}", byte.x, byte.y)
+}
+
+pub fn part1(input: &str, grid_size: Matrix, bytes_count: usize) -> usize {
+  let bytes = parse_input(input);
+  let corrupted = get_corrupted(&bytes, grid_size, bytes_count);
+  let result = find_shortest_path(&corrupted, grid_size).unwrap();
+  result
+}
+
+pub fn part2(input: &str, grid_size: Matrix) -> String {
+  let bytes = parse_input(input);
+  let result = format_byte(&find_first_blocking_byte_dsu(&bytes, grid_size).unwrap());
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = "5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0
+";
+
+  const GRID_SIZE: Matrix = Matrix { x: 7, y: 7 };
+
+  #[test]
+  fn test_part1() {
+    assert_eq!(part1(EXAMPLE, GRID_SIZE, 12), 22);
+  }
+
+  #[test]
+  fn test_part2() {
+    assert_eq!(part2(EXAMPLE, GRID_SIZE), "6,1");
+
+    let bytes = parse_input(EXAMPLE);
+    let by_binary_search = find_first_blocking_byte_binary_search(&bytes, GRID_SIZE);
+    assert_eq!(by_binary_search, Some(Matrix { x: 6, y: 1 }));
+  }
+
+  #[test]
+  fn test_part2_never_blocked() {
+    let bytes = parse_input("1,1\n2,2\n1,1\n");
+    assert_eq!(find_first_blocking_byte_dsu(&bytes, GRID_SIZE), None);
+    assert_eq!(find_first_blocking_byte_binary_search(&bytes, GRID_SIZE), None);
+  }
+}
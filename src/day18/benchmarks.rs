@@ -0,0 +1,38 @@
+use super::*;
+use crate::util::io::{bench_spacer, Env};
+
+#[derive(Debug)]
+enum BenchImpl {
+  Part1Bfs,
+  Part2BinarySearch,
+  Part2Dsu,
+}
+
+fn run_benchmark(input: &str, grid_size: Matrix, bytes_count: usize, fn_impl: BenchImpl) {
+  let now = std::time::Instant::now();
+  let result = match fn_impl {
+    BenchImpl::Part1Bfs => {
+      let result = part1(input, grid_size, bytes_count).to_string();
+      result
+    }
+    BenchImpl::Part2BinarySearch => {
+      let bytes = parse_input(input);
+      let result = format_byte(&find_first_blocking_byte_binary_search(&bytes, grid_size).unwrap());
+      result
+    }
+    BenchImpl::Part2Dsu => {
+      let bytes = parse_input(input);
+      let result = format_byte(&find_first_blocking_byte_dsu(&bytes, grid_size).unwrap());
+      result
+    }
+  };
+  let elapsed = now.elapsed();
+  println!("[{fn_impl:#?}] Elapsed {elapsed:.2?}; Result: {result}");
+}
+
+pub fn run(input: &str, grid_size: Matrix, bytes_count: usize, env: Env) {
+  bench_spacer(env);
+  run_benchmark(input, grid_size, bytes_count, BenchImpl::Part1Bfs);
+  run_benchmark(input, grid_size, bytes_count, BenchImpl::Part2BinarySearch);
+  run_benchmark(input, grid_size, bytes_count, BenchImpl::Part2Dsu);
+}
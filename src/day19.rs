@@ -0,0 +1,50 @@
+pub mod benchmarks;
+pub mod via_hashmap;
+pub mod via_recursion;
+pub mod via_trie;
+
+fn parse_input(input: &str) -> (Vec<&str>, Vec<&str>) {
+  let (patterns, designs) = input.trim().split_once("\n\n").unwrap();
+  let patterns = patterns.split(", ").collect();
+  let designs = designs.lines().collect();
+  (patterns, designs)
+}
+
+pub fn part1(input: &str) -> usize {
+  via_trie::part1(input)
+}
+
+pub fn part2(input: &str) -> usize {
+  via_trie::part2(input)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = "r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrwb
+";
+
+  #[test]
+  fn test_part1() {
+    assert_eq!(via_recursion::part1(EXAMPLE), 6);
+    assert_eq!(via_hashmap::part1(EXAMPLE), 6);
+    assert_eq!(via_trie::part1(EXAMPLE), 6);
+  }
+
+  #[test]
+  fn test_part2() {
+    assert_eq!(via_recursion::part2(EXAMPLE), 16);
+    assert_eq!(via_hashmap::part2(EXAMPLE), 16);
+    assert_eq!(via_trie::part2(EXAMPLE), 16);
+  }
+}
@@ -0,0 +1,63 @@
+use crate::day19::via_hashmap;
+use crate::day19::via_recursion;
+use crate::day19::via_trie;
+use crate::util::io::{bench_spacer, Env};
+
+#[derive(Debug)]
+enum BenchImpl {
+  Part1Recursion,
+  Part1HashMap,
+  Part1Trie,
+  Part2Recursion,
+  Part2HashMap,
+  Part2Trie,
+}
+
+fn run_benchmark(input: &str, fn_impl: BenchImpl) {
+  let now = std::time::Instant::now();
+  let result = match fn_impl {
+    BenchImpl::Part1Recursion => {
+      let result = via_recursion::part1(input);
+      result
+    }
+    BenchImpl::Part1HashMap => {
+      let result = via_hashmap::part1(input);
+      result
+    }
+    BenchImpl::Part1Trie => {
+      let result = via_trie::part1(input);
+      result
+    }
+    BenchImpl::Part2Recursion => {
+      let result = via_recursion::part2(input);
+      result
+    }
+    BenchImpl::Part2HashMap => {
+      let result = via_hashmap::part2(input);
+      result
+    }
+    BenchImpl::Part2Trie => {
+      let result = via_trie::part2(input);
+      result
+    }
+  };
+  let elapsed = now.elapsed();
+  println!("[{fn_impl:#?}] Elapsed {elapsed:.2?}; Result: {result}");
+}
+
+pub fn run(input: &str, env: Env) {
+  // ## the naive recursion is exponential in the design length, only the example input is small enough for it
+  let with_naive_recursion = matches!(env, Env::Test);
+  bench_spacer(env);
+
+  if with_naive_recursion {
+    run_benchmark(input, BenchImpl::Part1Recursion);
+  }
+  run_benchmark(input, BenchImpl::Part1HashMap);
+  run_benchmark(input, BenchImpl::Part1Trie);
+  if with_naive_recursion {
+    run_benchmark(input, BenchImpl::Part2Recursion);
+  }
+  run_benchmark(input, BenchImpl::Part2HashMap);
+  run_benchmark(input, BenchImpl::Part2Trie);
+}
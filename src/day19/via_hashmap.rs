@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+
+use super::parse_input;
+
+fn count_arrangements<'a>(design: &'a str, patterns: &Vec<&str>, memo: &mut HashMap<&'a str, usize>) -> usize {
+  if design.is_empty() {
+    return 1;
+  }
+  if let Some(&count) = memo.get(design) {
+    return count;
+  }
+
+  let count = patterns
+    .iter()
+    .filter(|p| design.starts_with(*p))
+    .map(|p| count_arrangements(&design[p.len()..], patterns, memo))
+    .sum();
+  memo.insert(design, count);
+  count
+}
+
+fn count_all_arrangements(input: &str) -> Vec<usize> {
+  let (patterns, designs) = parse_input(input);
+  // ## suffixes are shared between designs, so the memo is kept for the whole input
+  let mut memo: HashMap<&str, usize> = HashMap::new();
+  designs
+    .iter()
+    .map(|d| count_arrangements(d, &patterns, &mut memo))
+    .collect()
+}
+
+pub fn part1(input: &str) -> usize {
+  let result = count_all_arrangements(input).into_iter().filter(|&c| c > 0).count();
+  result
+}
+
+pub fn part2(input: &str) -> usize {
+  let result = count_all_arrangements(input).into_iter().sum();
+  result
+}
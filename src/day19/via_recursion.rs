@@ -0,0 +1,33 @@
+use super::parse_input;
+
+fn can_build(design: &str, patterns: &Vec<&str>) -> bool {
+  if design.is_empty() {
+    return true;
+  }
+  patterns
+    .iter()
+    .any(|p| design.starts_with(p) && can_build(&design[p.len()..], patterns))
+}
+
+fn count_arrangements(design: &str, patterns: &Vec<&str>) -> usize {
+  if design.is_empty() {
+    return 1;
+  }
+  patterns
+    .iter()
+    .filter(|p| design.starts_with(*p))
+    .map(|p| count_arrangements(&design[p.len()..], patterns))
+    .sum()
+}
+
+pub fn part1(input: &str) -> usize {
+  let (patterns, designs) = parse_input(input);
+  let result = designs.iter().filter(|d| can_build(d, &patterns)).count();
+  result
+}
+
+pub fn part2(input: &str) -> usize {
+  let (patterns, designs) = parse_input(input);
+  let result = designs.iter().map(|d| count_arrangements(d, &patterns)).sum();
+  result
+}
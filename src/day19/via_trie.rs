@@ -0,0 +1,86 @@
+use super::parse_input;
+
+const ALPHABET_SIZE: usize = 26;
+
+#[derive(Debug, Default)]
+struct TrieNode {
+  children: [Option<usize>; ALPHABET_SIZE],
+  is_pattern_end: bool,
+}
+
+// Towel patterns indexed by their stripes, so all the patterns matching at a position are found in a single walk
+#[derive(Debug)]
+struct Trie {
+  nodes: Vec<TrieNode>,
+}
+
+impl Trie {
+  fn new(patterns: &Vec<&str>) -> Self {
+    let mut trie = Trie {
+      nodes: vec![TrieNode::default()],
+    };
+    patterns.iter().for_each(|p| trie.insert(p));
+    trie
+  }
+
+  fn insert(&mut self, pattern: &str) {
+    let mut node = 0;
+    for stripe in pattern.bytes() {
+      let stripe = (stripe - b'a') as usize;
+      node = match self.nodes[node].children[stripe] {
+        Some(child) => child,
+        None => {
+          self.nodes.push(TrieNode::default());
+          let child = self.nodes.len() - 1;
+          self.nodes[node].children[stripe] = Some(child);
+          child
+        }
+      };
+    }
+    self.nodes[node].is_pattern_end = true;
+  }
+
+  // Lengths of all the patterns the design starts with
+  fn matching_lengths<'a>(&'a self, design: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+    design
+      .iter()
+      .scan(0, |node, &stripe| {
+        *node = self.nodes[*node].children[(stripe - b'a') as usize]?;
+        Some(*node)
+      })
+      .enumerate()
+      .filter(|(_, node)| self.nodes[*node].is_pattern_end)
+      .map(|(idx, _)| idx + 1)
+  }
+}
+
+// ## arrangements[i] counts the ways to build the design's suffix starting at `i`
+fn count_arrangements(design: &str, trie: &Trie) -> usize {
+  let design = design.as_bytes();
+  let mut arrangements = vec![0; design.len() + 1];
+  arrangements[design.len()] = 1;
+
+  for start in (0..design.len()).rev() {
+    arrangements[start] = trie
+      .matching_lengths(&design[start..])
+      .map(|len| arrangements[start + len])
+      .sum();
+  }
+  arrangements[0]
+}
+
+fn count_all_arrangements(input: &str) -> Vec<usize> {
+  let (patterns, designs) = parse_input(input);
+  let trie = Trie::new(&patterns);
+  designs.iter().map(|d| count_arrangements(d, &trie)).collect()
+}
+
+pub fn part1(input: &str) -> usize {
+  let result = count_all_arrangements(input).into_iter().filter(|&c| c > 0).count();
+  result
+}
+
+pub fn part2(input: &str) -> usize {
+  let result = count_all_arrangements(input).into_iter().sum();
+  result
+}
@@ -1,20 +1,45 @@
+use std::fmt;
+
+use crate::util::checked::{self, ArithmeticError};
+
 pub mod benchmarks;
+#[cfg(feature = "bigint")]
+pub mod bigint;
 
-fn parse_input(input: &str) -> Vec<Vec<u8>> {
+fn parse_input(input: &str) -> Result<Vec<Vec<u8>>, ArithmeticError> {
   let parsed_input = input
     .split("\n")
     .filter(|x| !x.is_empty())
-    .map(|line| {
-      line
-        .split_whitespace()
-        .map(|x| x.parse::<u8>().unwrap())
-        .collect::<Vec<u8>>()
-    })
-    .collect::<Vec<Vec<u8>>>();
+    .map(|line| line.split_whitespace().map(checked::try_parse::<u8>).collect())
+    .collect::<Result<Vec<Vec<u8>>, ArithmeticError>>();
   return parsed_input;
 }
 
-fn validate_line_item(line_item: &u8, prev_line_item: &u8, expected_direction: std::cmp::Ordering) -> bool {
+// Allowed absolute difference between adjacent levels, inclusive on both ends
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StepBounds {
+  pub min: u8,
+  pub max: u8,
+}
+
+impl Default for StepBounds {
+  fn default() -> Self {
+    Self { min: 1, max: 3 }
+  }
+}
+
+impl StepBounds {
+  fn contains(&self, diff: u8) -> bool {
+    self.min <= diff && diff <= self.max
+  }
+}
+
+fn validate_line_item(
+  line_item: &u8,
+  prev_line_item: &u8,
+  expected_direction: std::cmp::Ordering,
+  bounds: &StepBounds,
+) -> bool {
   let level_direction = line_item.cmp(&prev_line_item);
   let diff = match level_direction {
     std::cmp::Ordering::Greater => line_item - prev_line_item,
@@ -22,52 +47,414 @@ fn validate_line_item(line_item: &u8, prev_line_item: &u8, expected_direction: s
     std::cmp::Ordering::Equal => 0,
   };
 
-  let is_safe_levels_direction = level_direction == expected_direction;
-  let is_safe_level_diff = 1 <= diff && diff <= 3;
+  // ## a zero step goes neither up nor down, only the bounds tell whether it's safe
+  let is_safe_levels_direction = level_direction == expected_direction || level_direction == std::cmp::Ordering::Equal;
+  let is_safe_level_diff = bounds.contains(diff);
   return is_safe_levels_direction && is_safe_level_diff;
 }
 
-fn validate_report_line(report_line: &Vec<u8>) -> bool {
-  let expected_direction = report_line[1].cmp(&report_line[0]);
+// ## the first step going up or down sets the direction
+fn get_expected_direction(report_line: &Vec<u8>) -> std::cmp::Ordering {
+  report_line
+    .windows(2)
+    .map(|w| w[1].cmp(&w[0]))
+    .find(|direction| direction.is_ne())
+    .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+fn validate_report_line_in_bounds(report_line: &Vec<u8>, bounds: &StepBounds) -> bool {
+  let expected_direction = get_expected_direction(report_line);
   (1..report_line.len()).fold(true, |is_line_safe, i| {
     let (line_item, prev_line_item) = (report_line[i], report_line[i - 1]);
-    return is_line_safe && validate_line_item(&line_item, &prev_line_item, expected_direction);
+    return is_line_safe && validate_line_item(&line_item, &prev_line_item, expected_direction, bounds);
   })
 }
 
-fn apply_line_validation_dampener(report_line: &Vec<u8>) -> bool {
+fn validate_report_line(report_line: &Vec<u8>) -> bool {
+  validate_report_line_in_bounds(report_line, &StepBounds::default())
+}
+
+fn apply_line_validation_dampener(report_line: &Vec<u8>, bounds: &StepBounds) -> bool {
   (0..report_line.len()).any(|i| {
     let mut report_line_clone = report_line.clone();
     report_line_clone.remove(i);
-    validate_report_line(&mut report_line_clone)
+    validate_report_line_in_bounds(&mut report_line_clone, bounds)
   })
 }
 
-fn validate_report_line_with_dampener(report_line: &Vec<u8>) -> bool {
-  match validate_report_line(report_line) {
+fn validate_report_line_with_dampener_in_bounds(report_line: &Vec<u8>, bounds: &StepBounds) -> bool {
+  match validate_report_line_in_bounds(report_line, bounds) {
     true => true,
-    false => apply_line_validation_dampener(report_line),
+    false => apply_line_validation_dampener(report_line, bounds),
+  }
+}
+
+fn validate_report_line_with_dampener(report_line: &Vec<u8>) -> bool {
+  validate_report_line_with_dampener_in_bounds(report_line, &StepBounds::default())
+}
+
+fn is_safe_step(prev_line_item: u8, line_item: u8, is_ascending: bool, bounds: &StepBounds) -> bool {
+  let is_safe_levels_direction = line_item == prev_line_item || (line_item > prev_line_item) == is_ascending;
+  is_safe_levels_direction && bounds.contains(line_item.abs_diff(prev_line_item))
+}
+
+// ## kept[i]: fewest levels removed before level `i` with the levels up to and including `i` being safe, given that
+// ## level `i` is kept; as at most `max_removals` levels may be removed, the previously kept level is one of the
+// ## `max_removals + 1` preceding ones, making the check O(n * k) instead of revalidating every removal combination
+fn count_min_removals(report_line: &Vec<u8>, is_ascending: bool, max_removals: usize, bounds: &StepBounds) -> usize {
+  let mut kept: Vec<usize> = vec![usize::MAX; report_line.len()];
+  for i in 0..report_line.len() {
+    kept[i] = i;
+    for j in i.saturating_sub(max_removals + 1)..i {
+      if kept[j] != usize::MAX && is_safe_step(report_line[j], report_line[i], is_ascending, bounds) {
+        kept[i] = kept[i].min(kept[j] + (i - j - 1));
+      }
+    }
+  }
+  (0..report_line.len())
+    .map(|i| kept[i] + (report_line.len() - 1 - i))
+    .min()
+    .unwrap_or(0)
+}
+
+fn validate_report_line_with_k_dampener(report_line: &Vec<u8>, max_removals: usize, bounds: &StepBounds) -> bool {
+  [true, false]
+    .into_iter()
+    .any(|is_ascending| count_min_removals(report_line, is_ascending, max_removals, bounds) <= max_removals)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Violation {
+  DirectionChange,
+  ZeroStep,
+  StepTooSmall { min: u8 },
+  StepTooLarge { max: u8 },
+}
+
+impl fmt::Display for Violation {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Violation::DirectionChange => write!(f, "direction change"),
+      Violation::ZeroStep => write!(f, "zero step"),
+      Violation::StepTooSmall { min } => write!(f, "step < {min}"),
+      Violation::StepTooLarge { max } => write!(f, "step > {max}"),
+    }
   }
 }
 
-fn count_safe_reports(reports_data: &Vec<Vec<u8>>, line_validator: impl Fn(&Vec<u8>) -> bool) -> u16 {
-  reports_data.into_iter().map(line_validator).filter(|x| *x).count() as u16
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+  Safe,
+  SafeWithRemoval { removed_idx: usize },
+  Unsafe { idx: usize, violation: Violation },
 }
 
-pub fn part1(input: &str) -> u16 {
-  let reports_data: Vec<Vec<u8>> = parse_input(input);
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportDiagnosis {
+  pub line: usize,
+  pub levels: Vec<u8>,
+  pub verdict: Verdict,
+}
+
+impl ReportDiagnosis {
+  pub fn is_safe(&self) -> bool {
+    !matches!(self.verdict, Verdict::Unsafe { .. })
+  }
+}
+
+impl fmt::Display for ReportDiagnosis {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let levels = self
+      .levels
+      .iter()
+      .map(|l| l.to_string())
+      .collect::<Vec<String>>()
+      .join(" ");
+    match self.verdict {
+      Verdict::Safe => write!(f, "line {}: {levels} => safe", self.line),
+      Verdict::SafeWithRemoval { removed_idx } => write!(
+        f,
+        "line {}: {levels} => safe after removing level #{removed_idx} ({})",
+        self.line, self.levels[removed_idx]
+      ),
+      Verdict::Unsafe { idx, violation } => {
+        write!(f, "line {}: {levels} => unsafe at level #{idx} ({}): {violation}", self.line, self.levels[idx])
+      }
+    }
+  }
+}
+
+// ## same rules as `validate_line_item`, reporting the first level breaking them
+fn find_violation(report_line: &Vec<u8>, bounds: &StepBounds) -> Option<(usize, Violation)> {
+  if report_line.len() < 2 {
+    return None;
+  }
+  let expected_direction = get_expected_direction(report_line);
+  (1..report_line.len()).find_map(|i| {
+    let (line_item, prev_line_item) = (report_line[i], report_line[i - 1]);
+    let level_direction = line_item.cmp(&prev_line_item);
+    let violation = match line_item.abs_diff(prev_line_item) {
+      0 if !bounds.contains(0) => Violation::ZeroStep,
+      _ if level_direction.is_ne() && level_direction != expected_direction => Violation::DirectionChange,
+      diff if diff < bounds.min => Violation::StepTooSmall { min: bounds.min },
+      diff if diff > bounds.max => Violation::StepTooLarge { max: bounds.max },
+      _ => return None,
+    };
+    Some((i, violation))
+  })
+}
+
+fn diagnose_report_line(report_line: &Vec<u8>, bounds: &StepBounds) -> Verdict {
+  match find_violation(report_line, bounds) {
+    None => Verdict::Safe,
+    Some((idx, violation)) => Verdict::Unsafe { idx, violation },
+  }
+}
+
+fn diagnose_report_line_with_dampener(report_line: &Vec<u8>, bounds: &StepBounds) -> Verdict {
+  let verdict = diagnose_report_line(report_line, bounds);
+  if verdict == Verdict::Safe {
+    return verdict;
+  }
+  let removed_idx = (0..report_line.len()).find(|&i| {
+    let mut report_line_clone = report_line.clone();
+    report_line_clone.remove(i);
+    find_violation(&report_line_clone, bounds).is_none()
+  });
+  match removed_idx {
+    Some(removed_idx) => Verdict::SafeWithRemoval { removed_idx },
+    None => verdict,
+  }
+}
+
+pub fn diagnose_reports(
+  input: &str,
+  with_dampener: bool,
+  bounds: StepBounds,
+) -> Result<Vec<ReportDiagnosis>, ArithmeticError> {
+  let line_diagnoser = match with_dampener {
+    true => diagnose_report_line_with_dampener,
+    false => diagnose_report_line,
+  };
+  input
+    .lines()
+    .enumerate()
+    .filter(|(_, l)| !l.is_empty())
+    .map(|(idx, l)| {
+      let levels: Vec<u8> = l
+        .split_whitespace()
+        .map(checked::try_parse::<u8>)
+        .collect::<Result<_, _>>()?;
+      Ok(ReportDiagnosis {
+        line: idx + 1,
+        verdict: line_diagnoser(&levels, &bounds),
+        levels,
+      })
+    })
+    .collect()
+}
+
+fn count_safe_reports(
+  reports_data: &Vec<Vec<u8>>,
+  line_validator: impl Fn(&Vec<u8>) -> bool,
+) -> Result<u16, ArithmeticError> {
+  checked::try_convert(reports_data.into_iter().map(line_validator).filter(|x| *x).count())
+}
+
+pub fn count_safe_dampened_reports(
+  input: &str,
+  max_removals: usize,
+  bounds: StepBounds,
+) -> Result<u16, ArithmeticError> {
+  let reports_data: Vec<Vec<u8>> = parse_input(input)?;
+  count_safe_reports(&reports_data, |r| validate_report_line_with_k_dampener(r, max_removals, &bounds))
+}
+
+pub fn part1(input: &str) -> Result<u16, ArithmeticError> {
+  let reports_data: Vec<Vec<u8>> = parse_input(input)?;
   count_safe_reports(&reports_data, validate_report_line)
 }
 
-pub fn part2(input: &str) -> u16 {
-  let reports_data: Vec<Vec<u8>> = parse_input(input);
-  count_safe_reports(&reports_data, validate_report_line_with_dampener)
+pub fn part2(input: &str) -> Result<u16, ArithmeticError> {
+  let reports_data: Vec<Vec<u8>> = parse_input(input)?;
+  count_safe_reports(&reports_data, |r| validate_report_line_with_k_dampener(r, 1, &StepBounds::default()))
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
+  const EXAMPLE: &str = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+
+  #[test]
+  fn it_diagnoses_reports() {
+    let verdicts: Vec<Verdict> = diagnose_reports(EXAMPLE, false, StepBounds::default())
+      .unwrap()
+      .into_iter()
+      .map(|d| d.verdict)
+      .collect();
+    assert_eq!(
+      verdicts,
+      vec![
+        Verdict::Safe,
+        Verdict::Unsafe {
+          idx: 2,
+          violation: Violation::StepTooLarge { max: 3 }
+        },
+        Verdict::Unsafe {
+          idx: 3,
+          violation: Violation::StepTooLarge { max: 3 }
+        },
+        Verdict::Unsafe {
+          idx: 2,
+          violation: Violation::DirectionChange
+        },
+        Verdict::Unsafe {
+          idx: 3,
+          violation: Violation::ZeroStep
+        },
+        Verdict::Safe,
+      ]
+    );
+
+    let verdicts: Vec<Verdict> = diagnose_reports(EXAMPLE, true, StepBounds::default())
+      .unwrap()
+      .into_iter()
+      .map(|d| d.verdict)
+      .collect();
+    assert_eq!(
+      verdicts,
+      vec![
+        Verdict::Safe,
+        Verdict::Unsafe {
+          idx: 2,
+          violation: Violation::StepTooLarge { max: 3 }
+        },
+        Verdict::Unsafe {
+          idx: 3,
+          violation: Violation::StepTooLarge { max: 3 }
+        },
+        Verdict::SafeWithRemoval { removed_idx: 1 },
+        Verdict::SafeWithRemoval { removed_idx: 2 },
+        Verdict::Safe,
+      ]
+    );
+
+    let diagnosis = &diagnose_reports(EXAMPLE, true, StepBounds::default()).unwrap()[3];
+    assert_eq!(diagnosis.to_string(), "line 4: 1 3 2 4 5 => safe after removing level #1 (3)");
+    let diagnosis = &diagnose_reports(EXAMPLE, false, StepBounds::default()).unwrap()[1];
+    assert_eq!(diagnosis.to_string(), "line 2: 1 2 7 8 9 => unsafe at level #2 (7): step > 3");
+
+    let bounds = StepBounds { min: 2, max: 4 };
+    let diagnoses = diagnose_reports(EXAMPLE, false, bounds).unwrap();
+    assert_eq!(diagnoses[1].to_string(), "line 2: 1 2 7 8 9 => unsafe at level #1 (2): step < 2");
+    assert_eq!(diagnoses[2].to_string(), "line 3: 9 7 6 2 1 => unsafe at level #2 (6): step < 2");
+    let diagnoses = diagnose_reports("1 5 11\n", false, bounds).unwrap();
+    assert_eq!(diagnoses[0].to_string(), "line 1: 1 5 11 => unsafe at level #2 (11): step > 4");
+  }
+
+  fn remove_levels_brute_force(report_line: &Vec<u8>, max_removals: usize, bounds: &StepBounds) -> bool {
+    let is_safe = [true, false].into_iter().any(|is_ascending| {
+      report_line
+        .windows(2)
+        .all(|w| is_safe_step(w[0], w[1], is_ascending, bounds))
+    });
+    is_safe
+      || max_removals > 0
+        && (0..report_line.len()).any(|i| {
+          let mut report_line_clone = report_line.clone();
+          report_line_clone.remove(i);
+          remove_levels_brute_force(&report_line_clone, max_removals - 1, bounds)
+        })
+  }
+
+  #[test]
+  fn it_validates_report_lines_with_k_dampener() {
+    let bounds = StepBounds::default();
+    parse_input(EXAMPLE).unwrap().iter().for_each(|r| {
+      assert_eq!(validate_report_line_with_k_dampener(r, 0, &bounds), validate_report_line(r));
+      assert_eq!(validate_report_line_with_k_dampener(r, 1, &bounds), validate_report_line_with_dampener(r));
+    });
+    assert_eq!(count_safe_dampened_reports(EXAMPLE, 1, bounds), Ok(4));
+    assert_eq!(count_safe_dampened_reports(EXAMPLE, 2, bounds), Ok(6));
+    assert_eq!(count_safe_dampened_reports(EXAMPLE, 0, StepBounds { min: 1, max: 5 }), Ok(4));
+
+    assert!(!validate_report_line_with_k_dampener(&vec![1, 9, 2, 8, 3], 1, &bounds));
+    assert!(validate_report_line_with_k_dampener(&vec![1, 9, 2, 8, 3], 2, &bounds));
+    assert!(validate_report_line_with_k_dampener(&vec![5, 5, 5], 2, &bounds));
+    assert!(validate_report_line_with_k_dampener(&vec![], 0, &bounds));
+
+    // ## cross-checked on every report of 5 levels from 1..=6 against removing the levels one by one
+    let levels: Vec<u8> = (1..=6).collect();
+    let mut report_line = vec![0; 5];
+    (0..6_usize.pow(5)).for_each(|n| {
+      (0..5).for_each(|i| report_line[i] = levels[n / 6_usize.pow(i as u32) % 6]);
+      (0..=3).for_each(|k| {
+        assert_eq!(
+          validate_report_line_with_k_dampener(&report_line, k, &bounds),
+          remove_levels_brute_force(&report_line, k, &bounds),
+          "{report_line:?} with {k} removals"
+        );
+      });
+    });
+  }
+
+  #[test]
+  fn it_treats_zero_steps_as_neutral() {
+    let bounds = StepBounds { min: 0, max: 3 };
+    [vec![1, 1, 2], vec![3, 3, 2, 2], vec![5, 5, 5]].iter().for_each(|r| {
+      assert!(validate_report_line_in_bounds(r, &bounds), "{r:?}");
+      assert!(validate_report_line_with_k_dampener(r, 0, &bounds), "{r:?}");
+      assert_eq!(diagnose_report_line(r, &bounds), Verdict::Safe, "{r:?}");
+    });
+    assert!(!validate_report_line_in_bounds(&vec![1, 1, 2, 1], &bounds));
+    assert!(validate_report_line_with_dampener_in_bounds(&vec![1, 1, 2, 1], &bounds));
+    assert!(!validate_report_line(&vec![1, 1, 2]));
+  }
+
+  #[test]
+  fn it_validates_in_any_bounds() {
+    // ## every report of 4 levels from 1..=5, against the DP and the diagnosis, for bounds including zero or not
+    let bounds_list = [
+      StepBounds::default(),
+      StepBounds { min: 0, max: 2 },
+      StepBounds { min: 2, max: 4 },
+    ];
+    let mut report_line = vec![0; 4];
+    (0..5_usize.pow(4)).for_each(|n| {
+      (0..4).for_each(|i| report_line[i] = 1 + (n / 5_usize.pow(i as u32) % 5) as u8);
+      bounds_list.iter().for_each(|bounds| {
+        let is_safe = validate_report_line_in_bounds(&report_line, bounds);
+        assert_eq!(is_safe, validate_report_line_with_k_dampener(&report_line, 0, bounds), "{report_line:?}");
+        assert_eq!(is_safe, diagnose_report_line(&report_line, bounds) == Verdict::Safe, "{report_line:?}");
+        assert_eq!(
+          validate_report_line_with_dampener_in_bounds(&report_line, bounds),
+          validate_report_line_with_k_dampener(&report_line, 1, bounds),
+          "{report_line:?}"
+        );
+      });
+    });
+  }
+
+  #[test]
+  fn it_agrees_with_validators() {
+    let reports_data = parse_input(EXAMPLE).unwrap();
+    diagnose_reports(EXAMPLE, false, StepBounds::default())
+      .unwrap()
+      .iter()
+      .zip(reports_data.iter())
+      .for_each(|(d, r)| {
+        assert_eq!(d.is_safe(), validate_report_line(r));
+      });
+    diagnose_reports(EXAMPLE, true, StepBounds::default())
+      .unwrap()
+      .iter()
+      .zip(reports_data.iter())
+      .for_each(|(d, r)| {
+        assert_eq!(d.is_safe(), validate_report_line_with_dampener(r));
+      });
+  }
+
   #[test]
   fn it_validates_report_lines() {
     // # ascending: safe line items
@@ -216,4 +603,11 @@ mod tests {
     // # descending: safe last line item direction
     assert_eq!(validate_report_line_with_dampener(&vec![21, 20, 17, 24]), true);
   }
+
+  #[test]
+  fn it_reports_bad_levels() {
+    assert_eq!(part1("1 2 3\n4 5 300\n"), Err(ArithmeticError::Overflow(String::from("300 (u8)"))));
+    assert!(matches!(part2("1 2 x\n"), Err(ArithmeticError::InvalidNumber(_))));
+    assert!(diagnose_reports("1 -2 3\n", true, StepBounds::default()).is_err());
+  }
 }
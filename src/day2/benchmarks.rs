@@ -6,16 +6,21 @@ use super::*;
 enum BenchImpl {
   Part1Std,
   Part2Std,
+  Part2Dp,
 }
 
 fn run_benchmark(input: &str, fn_impl: BenchImpl) {
-  let mut reports_data: Vec<Vec<u8>> = parse_input(input);
+  let mut reports_data: Vec<Vec<u8>> = parse_input(input).unwrap();
   // println!("parsed_input: {:?}", reports_data);
 
   let now = std::time::Instant::now();
   let result = match fn_impl {
-    BenchImpl::Part1Std => count_safe_reports(&mut reports_data, validate_report_line),
-    BenchImpl::Part2Std => count_safe_reports(&mut reports_data, validate_report_line_with_dampener),
+    BenchImpl::Part1Std => count_safe_reports(&mut reports_data, validate_report_line).unwrap(),
+    BenchImpl::Part2Std => count_safe_reports(&mut reports_data, validate_report_line_with_dampener).unwrap(),
+    BenchImpl::Part2Dp => {
+      count_safe_reports(&mut reports_data, |r| validate_report_line_with_k_dampener(r, 1, &StepBounds::default()))
+        .unwrap()
+    }
   };
   let elapsed = now.elapsed();
   println!("[{fn_impl:#?}] Elapsed {elapsed:.2?}; Result: {result}");
@@ -25,4 +30,5 @@ pub fn run(input: &str, env: Env) {
   bench_spacer(env);
   run_benchmark(input, BenchImpl::Part1Std);
   run_benchmark(input, BenchImpl::Part2Std);
+  run_benchmark(input, BenchImpl::Part2Dp);
 }
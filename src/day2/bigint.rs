@@ -0,0 +1,30 @@
+use num_bigint::BigUint;
+
+use super::*;
+
+// Arbitrary-precision counts for scaled-up stress inputs with more reports than fit into `u16`
+fn count_safe_reports_big(reports_data: &Vec<Vec<u8>>, line_validator: impl Fn(&Vec<u8>) -> bool) -> BigUint {
+  BigUint::from(reports_data.iter().map(line_validator).filter(|x| *x).count())
+}
+
+pub fn part1(input: &str) -> Result<BigUint, ArithmeticError> {
+  let reports_data: Vec<Vec<u8>> = parse_input(input)?;
+  Ok(count_safe_reports_big(&reports_data, validate_report_line))
+}
+
+pub fn part2(input: &str) -> Result<BigUint, ArithmeticError> {
+  let reports_data: Vec<Vec<u8>> = parse_input(input)?;
+  Ok(count_safe_reports_big(&reports_data, validate_report_line_with_dampener))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_beyond_u16() {
+    let input = "1 2 3 4\n".repeat(70_000);
+    assert!(super::super::part1(&input).is_err());
+    assert_eq!(part1(&input), Ok(BigUint::from(70_000_u32)));
+  }
+}
@@ -0,0 +1,144 @@
+pub mod benchmarks;
+
+#[derive(Debug, Eq, Hash, PartialEq, Copy, Clone)]
+struct Pos {
+  i: usize,
+  j: usize,
+}
+
+const DIFFS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+fn find_marker(grid: &Vec<Vec<char>>, marker: char) -> Pos {
+  grid
+    .iter()
+    .enumerate()
+    .find_map(|(i, row)| row.iter().position(|&c| c == marker).map(|j| Pos { i, j }))
+    .unwrap()
+}
+
+fn parse_input(input: &str) -> Vec<Vec<char>> {
+  input.trim().lines().map(|l| l.chars().collect()).collect()
+}
+
+fn move_by(pos: &Pos, (di, dj): (isize, isize), grid: &Vec<Vec<char>>) -> Option<Pos> {
+  let (i, j) = (pos.i as isize + di, pos.j as isize + dj);
+  let is_inside = i >= 0 && j >= 0 && (i as usize) < grid.len() && (j as usize) < grid[0].len();
+  is_inside.then_some(Pos {
+    i: i as usize,
+    j: j as usize,
+  })
+}
+
+// ## there's a single track without forks, so walking it gives every track tile its distance from the start
+fn measure_track(grid: &Vec<Vec<char>>) -> (Vec<Pos>, Vec<Vec<Option<usize>>>) {
+  let (start, end) = (find_marker(grid, 'S'), find_marker(grid, 'E'));
+  let mut distances: Vec<Vec<Option<usize>>> = vec![vec![None; grid[0].len()]; grid.len()];
+  let mut track: Vec<Pos> = vec![start];
+  distances[start.i][start.j] = Some(0);
+
+  let mut current = start;
+  while current != end {
+    current = DIFFS
+      .iter()
+      .filter_map(|diff| move_by(&current, *diff, grid))
+      .find(|p| grid[p.i][p.j] != '#' && distances[p.i][p.j].is_none())
+      .expect("CRITICAL: the track ends before reaching the end tile");
+    distances[current.i][current.j] = Some(track.len());
+    track.push(current);
+  }
+  (track, distances)
+}
+
+// Going through a wall takes at least 2 moves, shorter "cheats" are staying put or plain track moves
+const MIN_CHEAT_LEN: usize = 2;
+
+// A cheat goes from a track tile to any track tile within `max_cheat_len` manhattan distance, saving the difference
+// between their distances along the track minus the cheat's own length
+fn count_cheats(grid: &Vec<Vec<char>>, max_cheat_len: usize, min_saving: usize) -> usize {
+  let (track, distances) = measure_track(grid);
+  let max_cheat_len = max_cheat_len as isize;
+
+  track
+    .iter()
+    .enumerate()
+    .map(|(from_dist, from)| {
+      let mut count = 0;
+      for di in -max_cheat_len..=max_cheat_len {
+        let dj_range = max_cheat_len - di.abs();
+        for dj in -dj_range..=dj_range {
+          let cheat_len = (di.abs() + dj.abs()) as usize;
+          if cheat_len < MIN_CHEAT_LEN {
+            continue;
+          }
+          let to_dist = move_by(from, (di, dj), grid).and_then(|to| distances[to.i][to.j]);
+          if let Some(to_dist) = to_dist {
+            if to_dist >= from_dist + cheat_len + min_saving {
+              count += 1;
+            }
+          }
+        }
+      }
+      count
+    })
+    .sum()
+}
+
+pub fn count_cheats_saving_at_least(input: &str, max_cheat_len: usize, min_saving: usize) -> usize {
+  let grid = parse_input(input);
+  count_cheats(&grid, max_cheat_len, min_saving)
+}
+
+pub fn part1(input: &str) -> usize {
+  let result = count_cheats_saving_at_least(input, 2, 100);
+  result
+}
+
+pub fn part2(input: &str) -> usize {
+  let result = count_cheats_saving_at_least(input, 20, 100);
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+";
+
+  #[test]
+  fn test_part1_example() {
+    assert_eq!(count_cheats_saving_at_least(EXAMPLE, 2, 1), 44);
+    assert_eq!(count_cheats_saving_at_least(EXAMPLE, 2, 20), 5);
+    assert_eq!(count_cheats_saving_at_least(EXAMPLE, 2, 64), 1);
+    assert_eq!(count_cheats_saving_at_least(EXAMPLE, 2, 65), 0);
+  }
+
+  #[test]
+  fn test_part2_example() {
+    assert_eq!(count_cheats_saving_at_least(EXAMPLE, 20, 50), 285);
+    assert_eq!(count_cheats_saving_at_least(EXAMPLE, 20, 76), 3);
+    assert_eq!(count_cheats_saving_at_least(EXAMPLE, 20, 77), 0);
+  }
+
+  #[test]
+  fn test_no_saving_threshold() {
+    // ## only S => E counts, saving nothing; staying put and single track moves aren't cheats
+    let input = "#####\n#S.E#\n#####\n";
+    assert_eq!(count_cheats_saving_at_least(input, 2, 0), 1);
+    assert_eq!(count_cheats_saving_at_least(input, 2, 1), 0);
+  }
+}
@@ -0,0 +1,30 @@
+use super::*;
+use crate::util::io::{bench_spacer, Env};
+
+#[derive(Debug)]
+enum BenchImpl {
+  Part1Std,
+  Part2Std,
+}
+
+fn run_benchmark(input: &str, min_saving: usize, fn_impl: BenchImpl) {
+  let now = std::time::Instant::now();
+  let result = match fn_impl {
+    BenchImpl::Part1Std => {
+      let result = count_cheats_saving_at_least(input, 2, min_saving);
+      result
+    }
+    BenchImpl::Part2Std => {
+      let result = count_cheats_saving_at_least(input, 20, min_saving);
+      result
+    }
+  };
+  let elapsed = now.elapsed();
+  println!("[{fn_impl:#?}] Elapsed {elapsed:.2?}; Result: {result}");
+}
+
+pub fn run(input: &str, min_saving: usize, env: Env) {
+  bench_spacer(env);
+  run_benchmark(input, min_saving, BenchImpl::Part1Std);
+  run_benchmark(input, min_saving, BenchImpl::Part2Std);
+}
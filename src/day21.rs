@@ -0,0 +1,195 @@
+pub mod benchmarks;
+
+use std::collections::HashMap;
+
+#[derive(Debug, Eq, Hash, PartialEq, Copy, Clone)]
+struct Pos {
+  i: i8,
+  j: i8,
+}
+
+#[derive(Debug)]
+struct Keypad {
+  keys: HashMap<char, Pos>,
+  gap: Pos,
+}
+
+impl Keypad {
+  fn from_layout(layout: &[&str]) -> Self {
+    let mut keys: HashMap<char, Pos> = HashMap::new();
+    let mut gap: Option<Pos> = None;
+    layout.iter().enumerate().for_each(|(i, row)| {
+      row.chars().enumerate().for_each(|(j, key)| {
+        let pos = Pos { i: i as i8, j: j as i8 };
+        match key {
+          ' ' => gap = Some(pos),
+          _ => {
+            keys.insert(key, pos);
+          }
+        }
+      })
+    });
+    Keypad {
+      keys,
+      gap: gap.expect("CRITICAL: keypad without a gap"),
+    }
+  }
+
+  fn numeric() -> Self {
+    Keypad::from_layout(&["789", "456", "123", " 0A"])
+  }
+
+  fn directional() -> Self {
+    Keypad::from_layout(&[" ^A", "<v>"])
+  }
+
+  // Button sequences moving the arm between two keys: all horizontal moves then all vertical moves or the other way
+  // round, as zigzagging is never cheaper; a sequence is dropped when its corner would be the gap
+  fn get_move_sequences(&self, from: char, to: char) -> Vec<Vec<char>> {
+    let (from, to) = (self.keys[&from], self.keys[&to]);
+    let (di, dj) = (to.i - from.i, to.j - from.j);
+    let vertical = vec![if di < 0 { '^' } else { 'v' }; di.unsigned_abs() as usize];
+    let horizontal = vec![if dj < 0 { '<' } else { '>' }; dj.unsigned_abs() as usize];
+
+    let mut sequences: Vec<Vec<char>> = vec![];
+    if (Pos { i: from.i, j: to.j }) != self.gap {
+      sequences.push([horizontal.clone(), vertical.clone(), vec!['A']].concat());
+    }
+    if (Pos { i: to.i, j: from.j }) != self.gap {
+      sequences.push([vertical, horizontal, vec!['A']].concat());
+    }
+    sequences.dedup();
+    sequences
+  }
+}
+
+struct KeypadChain {
+  numeric: Keypad,
+  directional: Keypad,
+  // ## (from, to, depth) => presses the human makes for the arm at `depth` to move between the keys and press
+  memo: HashMap<(char, char, usize), usize>,
+}
+
+impl KeypadChain {
+  fn new() -> Self {
+    KeypadChain {
+      numeric: Keypad::numeric(),
+      directional: Keypad::directional(),
+      memo: HashMap::new(),
+    }
+  }
+
+  // Presses the human makes for a sequence typed on a directional keypad with `depth` robots above it
+  fn sequence_cost(&mut self, sequence: &Vec<char>, depth: usize) -> usize {
+    if depth == 0 {
+      return sequence.len();
+    }
+    // ## every arm starts on and returns to `A` between presses of the keypad below
+    std::iter::once(&'A')
+      .chain(sequence.iter())
+      .zip(sequence.iter())
+      .map(|(&from, &to)| self.move_cost(from, to, depth))
+      .sum()
+  }
+
+  fn move_cost(&mut self, from: char, to: char, depth: usize) -> usize {
+    if let Some(&cost) = self.memo.get(&(from, to, depth)) {
+      return cost;
+    }
+    let cost = self
+      .directional
+      .get_move_sequences(from, to)
+      .iter()
+      .map(|seq| self.sequence_cost(seq, depth - 1))
+      .min()
+      .unwrap();
+    self.memo.insert((from, to, depth), cost);
+    cost
+  }
+
+  fn code_cost(&mut self, code: &str, robots: usize) -> usize {
+    let code: Vec<char> = code.chars().collect();
+    std::iter::once(&'A')
+      .chain(code.iter())
+      .zip(code.iter())
+      .map(|(&from, &to)| {
+        self
+          .numeric
+          .get_move_sequences(from, to)
+          .iter()
+          .map(|seq| self.sequence_cost(seq, robots))
+          .min()
+          .unwrap()
+      })
+      .sum()
+  }
+}
+
+fn parse_input(input: &str) -> Vec<&str> {
+  input.trim().lines().collect()
+}
+
+fn get_numeric_part(code: &str) -> usize {
+  code.trim_end_matches('A').parse::<usize>().unwrap()
+}
+
+// Sum of the codes complexities, with `robots` directional keypads between the human and the numeric keypad's robot
+pub fn sum_complexities(input: &str, robots: usize) -> usize {
+  let mut chain = KeypadChain::new();
+  parse_input(input)
+    .iter()
+    .map(|code| chain.code_cost(code, robots) * get_numeric_part(code))
+    .sum()
+}
+
+pub fn part1(input: &str) -> usize {
+  let result = sum_complexities(input, 2);
+  result
+}
+
+pub fn part2(input: &str) -> usize {
+  let result = sum_complexities(input, 25);
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = "029A
+980A
+179A
+456A
+379A
+";
+
+  #[test]
+  fn test_code_cost() {
+    let mut chain = KeypadChain::new();
+    assert_eq!(chain.code_cost("029A", 0), 12);
+    assert_eq!(chain.code_cost("029A", 1), 28);
+    assert_eq!(chain.code_cost("029A", 2), 68);
+    assert_eq!(chain.code_cost("379A", 2), 64);
+  }
+
+  #[test]
+  fn test_gap_avoidance() {
+    let numeric = Keypad::numeric();
+    assert_eq!(numeric.get_move_sequences('A', '1'), vec![vec!['^', '<', '<', 'A']]);
+    assert_eq!(numeric.get_move_sequences('7', '0'), vec![vec!['>', 'v', 'v', 'v', 'A']]);
+
+    let directional = Keypad::directional();
+    assert_eq!(directional.get_move_sequences('<', 'A'), vec![vec!['>', '>', '^', 'A']]);
+    assert_eq!(directional.get_move_sequences('A', 'A'), vec![vec!['A']]);
+  }
+
+  #[test]
+  fn test_part1() {
+    assert_eq!(part1(EXAMPLE), 126384);
+  }
+
+  #[test]
+  fn test_part2() {
+    assert_eq!(part2(EXAMPLE), 154115708116294);
+  }
+}
@@ -1,5 +1,7 @@
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+
+use crate::util::bitset::{self, BitSet};
 
 pub mod benchmarks;
 
@@ -75,8 +77,8 @@ fn calculate_sequence(num: usize, seq_len: usize) -> (i8, i8, i8, i8) {
 fn collect_sequences_results(
   initial_numbers: &Vec<usize>,
   secrets_per_day: usize,
-) -> (Vec<HashMap<(i8, i8, i8, i8), usize>>, HashSet<(i8, i8, i8, i8)>) {
-  let mut uniq_seqs: HashSet<(i8, i8, i8, i8)> = HashSet::new();
+) -> (Vec<HashMap<(i8, i8, i8, i8), usize>>, BitSet) {
+  let mut uniq_seqs = BitSet::with_capacity(bitset::DIFF_SEQ_DOMAIN);
 
   let buyers_seqs: Vec<HashMap<(i8, i8, i8, i8), usize>> = initial_numbers
     .into_iter()
@@ -97,7 +99,7 @@ fn collect_sequences_results(
         // mini-optimization: if last diff is less than 0, no need to check that
         // seq as its price is definitely less than in the previous iteration
         if seq.3 >= 0 {
-          uniq_seqs.insert(seq);
+          uniq_seqs.insert(bitset::encode_diff_seq(seq));
         }
 
         num = calculate_next_secret(num);
@@ -109,6 +111,33 @@ fn collect_sequences_results(
   (buyers_seqs, uniq_seqs)
 }
 
+// Dense variant: sequences are encoded into 0..19^4, each buyer tracks the sequences it has already
+// sold at in a bitset, and prices get summed straight into a flat totals vector
+fn calculate_max_sequence_price_dense(initial_numbers: &Vec<usize>, secrets_per_day: usize) -> usize {
+  let mut totals: Vec<usize> = vec![0; bitset::DIFF_SEQ_DOMAIN];
+  let mut seen = BitSet::with_capacity(bitset::DIFF_SEQ_DOMAIN);
+
+  initial_numbers.iter().for_each(|&initial_num| {
+    seen.clear();
+    let mut prices = vec![initial_num % 10];
+    let mut num = initial_num;
+    for _ in 0..secrets_per_day {
+      num = calculate_next_secret(num);
+      prices.push(num % 10);
+    }
+
+    prices.windows(SEQ_LEN + 1).for_each(|w| {
+      let diff = |i: usize| w[i + 1] as i8 - w[i] as i8;
+      let seq_idx = bitset::encode_diff_seq((diff(0), diff(1), diff(2), diff(3)));
+      if seen.insert(seq_idx) {
+        totals[seq_idx] += w[SEQ_LEN];
+      }
+    });
+  });
+
+  totals.into_iter().max().unwrap()
+}
+
 const SECRETS_PER_DAY: usize = 2000;
 const SEQ_LEN: usize = 4;
 
@@ -128,7 +157,8 @@ pub fn part2(input: &str) -> usize {
   //   and sum up its prices
   // max price is the result
   let max_sum: usize = uniq_seqs
-    .into_iter()
+    .iter()
+    .map(bitset::decode_diff_seq)
     .map(|seq| {
       buyers_seqs
         .iter()
@@ -201,4 +231,13 @@ mod tests {
     let result = calculate_sequence(num, SEQ_LEN);
     assert_eq!(result, (-1, 0, 2, -2));
   }
+
+  #[test]
+  fn test_part2_examples() {
+    let input = "1\n2\n3\n2024\n";
+    assert_eq!(part2(input), 23);
+
+    let initial_numbers = parse_input(input);
+    assert_eq!(calculate_max_sequence_price_dense(&initial_numbers, SECRETS_PER_DAY), 23);
+  }
 }
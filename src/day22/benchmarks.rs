@@ -5,6 +5,7 @@ use crate::util::io::{bench_spacer, Env};
 enum BenchImpl {
   Part1Std,
   Part2Std,
+  Part2Dense,
 }
 
 fn run_benchmark(input: &str, fn_impl: BenchImpl) {
@@ -18,6 +19,11 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
       let result = part2(input);
       result
     }
+    BenchImpl::Part2Dense => {
+      let initial_numbers = parse_input(input);
+      let result = calculate_max_sequence_price_dense(&initial_numbers, SECRETS_PER_DAY);
+      result
+    }
   };
   let elapsed = now.elapsed();
   println!("[{fn_impl:#?}] Elapsed {elapsed:.2?}; Result: {result}");
@@ -27,4 +33,5 @@ pub fn run(input: &str, env: Env) {
   bench_spacer(env);
   run_benchmark(input, BenchImpl::Part1Std);
   run_benchmark(input, BenchImpl::Part2Std);
+  run_benchmark(input, BenchImpl::Part2Dense);
 }
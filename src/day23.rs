@@ -5,6 +5,8 @@ use std::hash::Hasher;
 
 use itertools::Itertools;
 
+use crate::util::bitset::{self, BitSet};
+
 pub mod benchmarks;
 
 #[derive(Debug)]
@@ -174,6 +176,83 @@ fn calculate_password(conn_set: &ConnSet) -> String {
   conn_set.into_iter().sorted().join(",")
 }
 
+// =====================================================================================================================
+// ## Bitset variant: computers are encoded into 0..676 and each one keeps its neighbours in a bitset
+
+fn build_adjacency_bitsets(connections: &Vec<Conn>) -> Vec<BitSet> {
+  let mut adjacency = vec![BitSet::with_capacity(bitset::TWO_LETTERS_DOMAIN); bitset::TWO_LETTERS_DOMAIN];
+  connections.iter().for_each(|conn| {
+    let (comp1, comp2) = (bitset::encode_two_letters(&conn.0), bitset::encode_two_letters(&conn.1));
+    adjacency[comp1].insert(comp2);
+    adjacency[comp2].insert(comp1);
+  });
+  adjacency
+}
+
+fn count_three_sets_with_t_bitset(adjacency: &Vec<BitSet>) -> usize {
+  let starts_with_t = |comp: usize| comp / 26 == (b't' - b'a') as usize;
+
+  (0..adjacency.len())
+    .map(|comp1| {
+      adjacency[comp1]
+        .iter()
+        .filter(|&comp2| comp2 > comp1)
+        .map(|comp2| {
+          (&adjacency[comp1] & &adjacency[comp2])
+            .iter()
+            .filter(|&comp3| comp3 > comp2)
+            .filter(|&comp3| starts_with_t(comp1) || starts_with_t(comp2) || starts_with_t(comp3))
+            .count()
+        })
+        .sum::<usize>()
+    })
+    .sum()
+}
+
+// Bron-Kerbosch with pivoting, keeping only the largest clique found
+fn find_largest_clique_bitset(
+  adjacency: &Vec<BitSet>,
+  clique: &mut Vec<usize>,
+  mut candidates: BitSet,
+  mut excluded: BitSet,
+  largest: &mut Vec<usize>,
+) {
+  if candidates.is_empty() {
+    if excluded.is_empty() && clique.len() > largest.len() {
+      *largest = clique.clone();
+    }
+    return;
+  }
+
+  let pivot = (&candidates | &excluded)
+    .iter()
+    .max_by_key(|&p| candidates.intersection_count(&adjacency[p]))
+    .unwrap();
+  let mut to_visit = candidates.clone();
+  to_visit.difference_with(&adjacency[pivot]);
+
+  for comp in to_visit.iter() {
+    clique.push(comp);
+    let next_candidates = &candidates & &adjacency[comp];
+    let next_excluded = &excluded & &adjacency[comp];
+    find_largest_clique_bitset(adjacency, clique, next_candidates, next_excluded, largest);
+    clique.pop();
+
+    candidates.remove(comp);
+    excluded.insert(comp);
+  }
+}
+
+fn calculate_password_bitset(adjacency: &Vec<BitSet>) -> String {
+  let computers: BitSet = (0..adjacency.len())
+    .filter(|&comp| !adjacency[comp].is_empty())
+    .collect();
+  let mut largest: Vec<usize> = vec![];
+  find_largest_clique_bitset(adjacency, &mut vec![], computers, BitSet::new(), &mut largest);
+
+  largest.into_iter().sorted().map(bitset::decode_two_letters).join(",")
+}
+
 pub fn part1(input: &str) -> usize {
   let connections = parse_input(input);
   let computers = extract_computers(&connections);
@@ -194,3 +273,25 @@ pub fn part2(input: &str) -> String {
   let result = calculate_password(&largest_set);
   result
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = "kh-tc\nqp-kh\nde-cg\nka-co\nyn-aq\nqp-ub\ncg-tb\nvc-aq\ntb-ka\nwh-tc\nyn-cg\nkh-ub\n\
+                         ta-co\nde-co\ntc-td\ntb-wq\nwh-td\nta-ka\ntd-qp\naq-cg\nwq-ub\nub-vc\nde-ta\nwq-aq\n\
+                         wq-vc\nwh-yn\nka-de\nkh-ta\nco-tc\nwh-qp\ntb-vc\ntd-yn\n";
+
+  #[test]
+  fn test_example() {
+    assert_eq!(part1(EXAMPLE), 7);
+    assert_eq!(part2(EXAMPLE), "co,de,ka,ta");
+  }
+
+  #[test]
+  fn test_example_bitset() {
+    let adjacency = build_adjacency_bitsets(&parse_input(EXAMPLE));
+    assert_eq!(count_three_sets_with_t_bitset(&adjacency), 7);
+    assert_eq!(calculate_password_bitset(&adjacency), "co,de,ka,ta");
+  }
+}
@@ -4,7 +4,9 @@ use crate::util::io::{bench_spacer, Env};
 #[derive(Debug)]
 enum BenchImpl {
   Part1Std,
+  Part1Bitset,
   Part2Std,
+  Part2Bitset,
 }
 
 fn run_benchmark(input: &str, fn_impl: BenchImpl) {
@@ -14,10 +16,20 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
       let result = part1(input).to_string();
       result
     }
+    BenchImpl::Part1Bitset => {
+      let adjacency = build_adjacency_bitsets(&parse_input(input));
+      let result = count_three_sets_with_t_bitset(&adjacency).to_string();
+      result
+    }
     BenchImpl::Part2Std => {
       let result = part2(input);
       result
     }
+    BenchImpl::Part2Bitset => {
+      let adjacency = build_adjacency_bitsets(&parse_input(input));
+      let result = calculate_password_bitset(&adjacency);
+      result
+    }
   };
   let elapsed = now.elapsed();
   println!("[{fn_impl:#?}] Elapsed {elapsed:.2?}; Result: {result}");
@@ -26,5 +38,7 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
 pub fn run(input: &str, env: Env) {
   bench_spacer(env);
   run_benchmark(input, BenchImpl::Part1Std);
+  run_benchmark(input, BenchImpl::Part1Bitset);
   run_benchmark(input, BenchImpl::Part2Std);
+  run_benchmark(input, BenchImpl::Part2Bitset);
 }
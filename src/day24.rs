@@ -1,11 +1,12 @@
 use itertools::Itertools;
 use regex::Regex;
 use std::collections::HashMap;
+use std::fmt;
 use std::str::FromStr;
 
 pub mod benchmarks;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 enum GateType {
   AND,
   OR,
@@ -125,9 +126,235 @@ fn get_end_result(wires: &HashMap<String, bool>) -> usize {
   usize::from_str_radix(binary_result.as_str(), 2).unwrap()
 }
 
+// =====================================================================================================================
+// ## Structural analysis of the ripple-carry adder, for every bit `i` (but the first) expected to be:
+// ##   xi XOR yi -> si, xi AND yi -> ai, si XOR ci-1 -> zi, si AND ci-1 -> bi, ai OR bi -> ci
+
+// The puzzle swaps exactly 4 pairs of gate outputs
+const SWAPPED_PAIRS: usize = 4;
+
+#[derive(Debug, PartialEq)]
+pub enum AdderError {
+  UnexpectedSwappedWires(Vec<String>),
+}
+
+impl fmt::Display for AdderError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      AdderError::UnexpectedSwappedWires(wires) => {
+        write!(f, "expected {} swapped wires, found {}: {}", SWAPPED_PAIRS * 2, wires.len(), wires.join(","))
+      }
+    }
+  }
+}
+
+impl std::error::Error for AdderError {}
+
+#[derive(Debug, PartialEq)]
+pub struct AdderReport {
+  pub swapped_wires: Vec<String>,
+  pub faulty_bits: Vec<u8>,
+}
+
+fn is_input_wire(wire: &str) -> bool {
+  wire.starts_with('x') || wire.starts_with('y')
+}
+
+fn wire_bit(wire: &str) -> Option<u8> {
+  wire[1..].parse::<u8>().ok()
+}
+
+fn get_wire_consumers(gates: &HashMap<String, Gate>) -> HashMap<&str, Vec<&GateType>> {
+  let mut consumers: HashMap<&str, Vec<&GateType>> = HashMap::new();
+  gates.values().for_each(|g| {
+    consumers.entry(g.in1.as_str()).or_default().push(&g.gate);
+    consumers.entry(g.in2.as_str()).or_default().push(&g.gate);
+  });
+  consumers
+}
+
+fn is_misplaced_gate_output(out: &str, gate: &Gate, last_z: &str, consumers: &HashMap<&str, Vec<&GateType>>) -> bool {
+  let feeds = |gate_type: GateType| consumers.get(out).is_some_and(|c| c.contains(&&gate_type));
+  let has_input_wires = is_input_wire(&gate.in1) && is_input_wire(&gate.in2);
+  let is_first_bit = [&gate.in1, &gate.in2].iter().all(|w| wire_bit(w) == Some(0));
+
+  match gate.gate {
+    // ## all the z outputs come from XOR gates, but the final carry which comes from an OR
+    _ if out.starts_with('z') && out != last_z => gate.gate != GateType::XOR,
+    _ if out == last_z => gate.gate != GateType::OR,
+    // ## XOR of carry and partial sum must output a z
+    GateType::XOR if !has_input_wires => true,
+    // ## partial sum XOR (x, y) must feed the next XOR, bit 0 being a half adder outputting z00 directly
+    GateType::XOR => !is_first_bit && !feeds(GateType::XOR),
+    // ## both carry ANDs feed the carry OR, bit 0 being a half adder whose AND is the carry itself
+    GateType::AND => !is_first_bit && !feeds(GateType::OR),
+    // ## the carry feeds both the XOR and the AND of the next bit
+    GateType::OR => !feeds(GateType::XOR) || !feeds(GateType::AND),
+  }
+}
+
+fn find_swapped_wires(gates: &HashMap<String, Gate>) -> Vec<String> {
+  let consumers = get_wire_consumers(gates);
+  let last_z = gates.keys().filter(|k| k.starts_with('z')).max().unwrap();
+
+  gates
+    .iter()
+    .filter(|(out, gate)| is_misplaced_gate_output(out, gate, last_z, &consumers))
+    .map(|(out, _)| out.to_string())
+    .sorted()
+    .collect()
+}
+
+// The bit a wire belongs to is the highest x/y input bit it depends on
+fn find_wire_bit(wire: &str, gates: &HashMap<String, Gate>, memo: &mut HashMap<String, u8>) -> u8 {
+  if is_input_wire(wire) {
+    return wire_bit(wire).unwrap();
+  }
+  if let Some(&bit) = memo.get(wire) {
+    return bit;
+  }
+
+  // ## a cross-bit swap can loop a carry back into itself, the placeholder stops the recursion there
+  memo.insert(wire.to_string(), 0);
+  let gate = &gates[wire];
+  let bit = std::cmp::max(find_wire_bit(&gate.in1, gates, memo), find_wire_bit(&gate.in2, gates, memo));
+  memo.insert(wire.to_string(), bit);
+  bit
+}
+
+fn find_faulty_bits(swapped_wires: &Vec<String>, gates: &HashMap<String, Gate>) -> Vec<u8> {
+  let mut memo: HashMap<String, u8> = HashMap::new();
+  swapped_wires
+    .iter()
+    .map(|w| match w.starts_with('z') {
+      true => wire_bit(w).unwrap(),
+      false => find_wire_bit(w, gates, &mut memo),
+    })
+    .unique()
+    .sorted()
+    .collect()
+}
+
+fn find_swapped_pairs(gates: &HashMap<String, Gate>) -> Result<Vec<String>, AdderError> {
+  let swapped_wires = find_swapped_wires(gates);
+  match swapped_wires.len() == SWAPPED_PAIRS * 2 {
+    true => Ok(swapped_wires),
+    false => Err(AdderError::UnexpectedSwappedWires(swapped_wires)),
+  }
+}
+
+pub fn analyze_adder(input: &str) -> Result<AdderReport, AdderError> {
+  let (_wires, gates) = parse_input(input);
+  let swapped_wires = find_swapped_pairs(&gates)?;
+  let faulty_bits = find_faulty_bits(&swapped_wires, &gates);
+  Ok(AdderReport {
+    swapped_wires,
+    faulty_bits,
+  })
+}
+
 pub fn part1(input: &str) -> usize {
   let (mut wires, mut gates) = parse_input(input);
   resolve_all_gates(&mut gates, &mut wires);
   let result = get_end_result(&wires);
   result
 }
+
+pub fn part2(input: &str) -> Result<String, AdderError> {
+  let (_wires, gates) = parse_input(input);
+  let swapped_wires = find_swapped_pairs(&gates)?;
+  let result = swapped_wires.join(",");
+  Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // A correct ripple-carry adder of `bits`-bit numbers, as in the puzzle input
+  fn build_adder(bits: usize, x: usize, y: usize) -> Vec<(String, String)> {
+    let mut lines: Vec<(String, String)> = vec![];
+    let mut gate = |in1: String, op: &str, in2: String, out: String| lines.push((format!("{in1} {op} {in2}"), out));
+
+    gate("x00".into(), "XOR", "y00".into(), "z00".into());
+    gate("x00".into(), "AND", "y00".into(), "c00".into());
+    for i in 1..bits {
+      let carry_out = match i == bits - 1 {
+        true => format!("z{:02}", bits),
+        false => format!("c{i:02}"),
+      };
+      gate(format!("x{i:02}"), "XOR", format!("y{i:02}"), format!("s{i:02}"));
+      gate(format!("x{i:02}"), "AND", format!("y{i:02}"), format!("a{i:02}"));
+      gate(format!("s{i:02}"), "XOR", format!("c{:02}", i - 1), format!("z{i:02}"));
+      gate(format!("s{i:02}"), "AND", format!("c{:02}", i - 1), format!("b{i:02}"));
+      gate(format!("a{i:02}"), "OR", format!("b{i:02}"), carry_out);
+    }
+
+    let mut wires: Vec<(String, String)> = vec![];
+    for i in 0..bits {
+      wires.push((format!("x{i:02}"), ((x >> i) & 1).to_string()));
+      wires.push((format!("y{i:02}"), ((y >> i) & 1).to_string()));
+    }
+    wires.into_iter().chain(lines).collect()
+  }
+
+  fn to_input(adder: &Vec<(String, String)>, swaps: &[(&str, &str)]) -> String {
+    let swap = |out: &String| {
+      swaps.iter().fold(out.to_string(), |out, (a, b)| match out.as_str() {
+        o if o == *a => b.to_string(),
+        o if o == *b => a.to_string(),
+        _ => out,
+      })
+    };
+    let init_wires = adder
+      .iter()
+      .filter(|(_, v)| v.len() == 1)
+      .map(|(k, v)| format!("{k}: {v}"))
+      .join("\n");
+    let gates = adder
+      .iter()
+      .filter(|(_, v)| v.len() == 3)
+      .map(|(g, out)| format!("{g} -> {}", swap(out)))
+      .join("\n");
+    format!("{init_wires}\n\n{gates}\n")
+  }
+
+  #[test]
+  fn test_correct_adder() {
+    let adder = build_adder(6, 27, 45);
+    let input = to_input(&adder, &[]);
+    assert_eq!(part1(&input), 72);
+    assert_eq!(part2(&input), Err(AdderError::UnexpectedSwappedWires(vec![])));
+  }
+
+  #[test]
+  fn test_swapped_adder() {
+    let adder = build_adder(6, 27, 45);
+    let input = to_input(&adder, &[("s02", "a02"), ("z04", "b04")]);
+    let (_wires, gates) = parse_input(&input);
+    let swapped_wires = find_swapped_wires(&gates);
+    assert_eq!(swapped_wires, vec!["a02", "b04", "s02", "z04"]);
+    assert_eq!(find_faulty_bits(&swapped_wires, &gates), vec![2, 4]);
+    assert_eq!(part2(&input), Err(AdderError::UnexpectedSwappedWires(swapped_wires)));
+  }
+
+  #[test]
+  fn test_four_swapped_pairs() {
+    // ## a03 <-> c05 crosses bits, moving an AND output onto a carry OR and back
+    let adder = build_adder(8, 27, 45);
+    let input = to_input(&adder, &[("a03", "c05"), ("s01", "a01"), ("z04", "b04"), ("z06", "c06")]);
+    assert_eq!(part2(&input).unwrap(), "a01,a03,b04,c05,c06,s01,z04,z06");
+    assert_eq!(
+      analyze_adder(&input),
+      Ok(AdderReport {
+        swapped_wires: ["a01", "a03", "b04", "c05", "c06", "s01", "z04", "z06"]
+          .map(String::from)
+          .to_vec(),
+        faulty_bits: vec![1, 3, 4, 5, 6],
+      })
+    );
+
+    let (_wires, gates) = parse_input(&to_input(&adder, &[("a03", "c05")]));
+    assert_eq!(find_swapped_wires(&gates), vec!["a03", "c05"]);
+  }
+}
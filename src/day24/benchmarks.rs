@@ -4,13 +4,18 @@ use crate::util::io::{bench_spacer, Env};
 #[derive(Debug)]
 enum BenchImpl {
   Part1Std,
+  Part2Std,
 }
 
 fn run_benchmark(input: &str, fn_impl: BenchImpl) {
   let now = std::time::Instant::now();
   let result = match fn_impl {
     BenchImpl::Part1Std => {
-      let result = part1(input);
+      let result = part1(input).to_string();
+      result
+    }
+    BenchImpl::Part2Std => {
+      let result = part2(input).unwrap_or_else(|err| err.to_string());
       result
     }
   };
@@ -21,4 +26,5 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
 pub fn run(input: &str, env: Env) {
   bench_spacer(env);
   run_benchmark(input, BenchImpl::Part1Std);
+  run_benchmark(input, BenchImpl::Part2Std);
 }
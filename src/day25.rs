@@ -1,10 +1,62 @@
 pub mod benchmarks;
 
-fn to_pin_heights<'a, I>(input: I) -> [u8; 5]
+use std::fmt;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Dimensions {
+  pub width: usize,
+  pub height: usize,
+}
+
+impl fmt::Display for Dimensions {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}x{}", self.width, self.height)
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseSchematicError {
+  EmptyInput,
+  TooShort {
+    idx: usize,
+  },
+  DimensionsMismatch {
+    idx: usize,
+    expected: Dimensions,
+    found: Dimensions,
+  },
+  NotLockNorKey {
+    idx: usize,
+  },
+}
+
+impl fmt::Display for ParseSchematicError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ParseSchematicError::EmptyInput => write!(f, "no schematics in the input"),
+      ParseSchematicError::TooShort { idx } => write!(f, "schematic #{idx}: expected at least 2 rows"),
+      ParseSchematicError::DimensionsMismatch { idx, expected, found } => {
+        write!(f, "schematic #{idx}: expected {expected} pins, found {found}")
+      }
+      ParseSchematicError::NotLockNorKey { idx } => write!(f, "schematic #{idx}: neither a lock nor a key"),
+    }
+  }
+}
+
+impl std::error::Error for ParseSchematicError {}
+
+#[derive(Debug)]
+struct Schematics {
+  dims: Dimensions,
+  locks: Vec<Vec<usize>>,
+  keys: Vec<Vec<usize>>,
+}
+
+fn to_pin_heights<'a, I>(input: I, width: usize) -> Vec<usize>
 where
   I: Iterator<Item = &'a str>,
 {
-  let mut pin_heights: [u8; 5] = [0; 5];
+  let mut pin_heights: Vec<usize> = vec![0; width];
   input.for_each(|l| {
     l.chars().enumerate().for_each(|(i, c)| {
       if c == '#' {
@@ -15,51 +67,221 @@ where
   return pin_heights;
 }
 
-fn parse_lock(input: &str) -> [u8; 5] {
-  to_pin_heights(input.lines().skip(1))
+// ## the first and last rows are the lock's base or the key's blade, leaving `rows - 2` for the pins
+fn get_dimensions(schematic: &str, idx: usize) -> Result<Dimensions, ParseSchematicError> {
+  let rows: Vec<&str> = schematic.lines().collect();
+  if rows.len() < 2 {
+    return Err(ParseSchematicError::TooShort { idx });
+  }
+  let width = rows[0].len();
+  if rows.iter().any(|r| r.len() != width) {
+    return Err(ParseSchematicError::DimensionsMismatch {
+      idx,
+      expected: Dimensions {
+        width,
+        height: rows.len() - 2,
+      },
+      found: Dimensions {
+        width: rows.iter().map(|r| r.len()).find(|&w| w != width).unwrap(),
+        height: rows.len() - 2,
+      },
+    });
+  }
+  Ok(Dimensions {
+    width,
+    height: rows.len() - 2,
+  })
 }
 
-fn parse_key(input: &str) -> [u8; 5] {
-  to_pin_heights(input.lines().rev().skip(1))
-}
+fn parse_input(input: &str) -> Result<Schematics, ParseSchematicError> {
+  let schematics: Vec<&str> = input.trim().split("\n\n").filter(|s| !s.is_empty()).collect();
+  let first = schematics.first().ok_or(ParseSchematicError::EmptyInput)?;
+  let dims = get_dimensions(first, 0)?;
 
-fn parse_input(input: &str) -> (Vec<[u8; 5]>, Vec<[u8; 5]>) {
-  let mut locks: Vec<[u8; 5]> = vec![];
-  let mut keys: Vec<[u8; 5]> = vec![];
+  let mut locks: Vec<Vec<usize>> = vec![];
+  let mut keys: Vec<Vec<usize>> = vec![];
+  for (idx, s) in schematics.iter().enumerate() {
+    let found = get_dimensions(s, idx)?;
+    if found != dims {
+      return Err(ParseSchematicError::DimensionsMismatch {
+        idx,
+        expected: dims,
+        found,
+      });
+    }
 
-  input.trim().split("\n\n").for_each(|s| {
-    if s.starts_with("#####") {
-      locks.push(parse_lock(s));
+    let (top, bottom) = (s.lines().next().unwrap(), s.lines().last().unwrap());
+    let is_filled = |row: &str| row.chars().all(|c| c == '#');
+    let is_empty = |row: &str| row.chars().all(|c| c == '.');
+    if is_filled(top) && is_empty(bottom) {
+      locks.push(to_pin_heights(s.lines().skip(1), dims.width));
+    } else if is_empty(top) && is_filled(bottom) {
+      keys.push(to_pin_heights(s.lines().rev().skip(1), dims.width));
     } else {
-      keys.push(parse_key(s));
+      return Err(ParseSchematicError::NotLockNorKey { idx });
     }
-  });
+  }
 
-  (keys, locks)
+  Ok(Schematics { dims, locks, keys })
 }
 
-fn count_non_overlapping(keys: Vec<[u8; 5]>, locks: Vec<[u8; 5]>) -> usize {
-  keys.into_iter().fold(0_usize, |acc, k| {
-    let non_overlapping = locks.iter().fold(0_usize, |acc, l| {
-      for i in 0..5 {
-        let overlaps = k[i] + l[i] > 5;
-        if overlaps {
-          return acc;
-        }
-      }
-      acc + 1
-    });
-    acc + non_overlapping
-  })
+fn fits(key: &Vec<usize>, lock: &Vec<usize>, dims: &Dimensions) -> bool {
+  key.iter().zip(lock.iter()).all(|(k, l)| k + l <= dims.height)
 }
 
-pub fn part1(input: &str) -> usize {
-  let (keys, locks) = parse_input(input);
-  let non_overlapping_count = count_non_overlapping(keys, locks);
-  non_overlapping_count
+fn count_non_overlapping(schematics: &Schematics) -> usize {
+  schematics
+    .keys
+    .iter()
+    .map(|k| schematics.locks.iter().filter(|l| fits(k, l, &schematics.dims)).count())
+    .sum()
 }
 
-pub fn part2(input: &str) -> usize {
-  let (keys, locks) = parse_input(input);
-  keys.len() + locks.len()
+// For every lock (in input order), the indices of the keys (in input order) fitting it
+#[derive(Debug, PartialEq)]
+pub struct FitSummary {
+  pub keys_per_lock: Vec<Vec<usize>>,
+}
+
+impl fmt::Display for FitSummary {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    self.keys_per_lock.iter().enumerate().try_for_each(|(lock, keys)| {
+      let keys = match keys.is_empty() {
+        true => String::from("-"),
+        false => keys.iter().map(|k| k.to_string()).collect::<Vec<String>>().join(", "),
+      };
+      writeln!(f, "Lock #{lock} <= Keys: {keys}")
+    })
+  }
+}
+
+fn summarize_fits(schematics: &Schematics) -> FitSummary {
+  let keys_per_lock = schematics
+    .locks
+    .iter()
+    .map(|l| {
+      schematics
+        .keys
+        .iter()
+        .enumerate()
+        .filter(|(_, k)| fits(k, l, &schematics.dims))
+        .map(|(idx, _)| idx)
+        .collect()
+    })
+    .collect();
+  FitSummary { keys_per_lock }
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseSchematicError> {
+  let schematics = parse_input(input)?;
+  let non_overlapping_count = count_non_overlapping(&schematics);
+  Ok(non_overlapping_count)
+}
+
+pub fn part2(input: &str) -> Result<FitSummary, ParseSchematicError> {
+  let schematics = parse_input(input)?;
+  let result = summarize_fits(&schematics);
+  Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = "#####
+.####
+.####
+.####
+.#.#.
+.#...
+.....
+
+#####
+##.##
+.#.##
+...##
+...#.
+...#.
+.....
+
+.....
+#....
+#....
+#...#
+#.#.#
+#.###
+#####
+
+.....
+.....
+#.#..
+###..
+###.#
+###.#
+#####
+
+.....
+.....
+.....
+#....
+#.#..
+#.#.#
+#####
+";
+
+  #[test]
+  fn test_part1() {
+    assert_eq!(part1(EXAMPLE), Ok(3));
+  }
+
+  #[test]
+  fn test_part2() {
+    let summary = part2(EXAMPLE).unwrap();
+    assert_eq!(summary.keys_per_lock, vec![vec![2], vec![1, 2]]);
+    assert_eq!(summary.to_string(), "Lock #0 <= Keys: 2\nLock #1 <= Keys: 1, 2\n");
+  }
+
+  #[test]
+  fn test_other_dimensions() {
+    let input = "###\n.#.\n...\n\n...\n#..\n###\n\n...\n.##\n###\n";
+    let schematics = parse_input(input).unwrap();
+    assert_eq!(schematics.dims, Dimensions { width: 3, height: 1 });
+    assert_eq!(count_non_overlapping(&schematics), 1);
+  }
+
+  #[test]
+  fn test_tall_schematics() {
+    // ## 300 pin rows, more than a u8 height can count
+    let rows = |top: &str, pins: Vec<&str>, bottom: &str| [vec![top], pins, vec![bottom]].concat().join("\n");
+    let lock = |filled: usize| rows("#", [vec!["#"; filled], vec!["."; 300 - filled]].concat(), ".");
+    let key = |filled: usize| rows(".", [vec!["."; 300 - filled], vec!["#"; filled]].concat(), "#");
+    let input = [lock(280), key(20), key(21)].join("\n\n");
+
+    let schematics = parse_input(&input).unwrap();
+    assert_eq!(schematics.dims, Dimensions { width: 1, height: 300 });
+    assert_eq!(schematics.locks, vec![vec![280]]);
+    assert_eq!(schematics.keys, vec![vec![20], vec![21]]);
+    assert_eq!(part1(&input), Ok(1));
+  }
+
+  #[test]
+  fn test_invalid_schematics() {
+    assert_eq!(parse_input("").unwrap_err(), ParseSchematicError::EmptyInput);
+    assert_eq!(
+      parse_input("###\n...\n...\n\n....\n....\n####\n").unwrap_err(),
+      ParseSchematicError::DimensionsMismatch {
+        idx: 1,
+        expected: Dimensions { width: 3, height: 1 },
+        found: Dimensions { width: 4, height: 1 },
+      }
+    );
+    assert_eq!(
+      parse_input("###\n...\n...\n\n#.#\n...\n###\n").unwrap_err(),
+      ParseSchematicError::NotLockNorKey { idx: 1 }
+    );
+
+    let err = part1("###\n...\n...\n\n....\n....\n####\n").unwrap_err();
+    assert_eq!(err.to_string(), "schematic #1: expected 3x1 pins, found 4x1");
+    assert!(part2("#\n").is_err());
+  }
 }
@@ -11,11 +11,12 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
   let now = std::time::Instant::now();
   let result = match fn_impl {
     BenchImpl::Part1Std => {
-      let result = part1(input);
+      let result = part1(input).unwrap();
       result
     }
     BenchImpl::Part2Std => {
-      let result = part2(input);
+      // ## keys fitting each lock add up to the part 1 count
+      let result = part2(input).unwrap().keys_per_lock.iter().map(|keys| keys.len()).sum();
       result
     }
   };
@@ -1,20 +1,50 @@
 use regex::Regex;
 
 pub mod benchmarks;
+pub mod byte_parser;
+pub mod highlight;
+pub mod interpreter;
 pub mod parser;
 pub mod preprocessor;
+pub mod stream;
+pub mod tokenizer;
 
-fn parse_input_regex(input: &str, matching_expr: &Regex) -> Vec<String> {
-  matching_expr.find_iter(input).map(|m| m.as_str().to_string()).collect()
+use parser::ParsedMatch;
+use tokenizer::Span;
+
+// Arguments are 1-3 digit numbers, the same rule the walkthrough parser, tokenizer and byte parser follow
+const MUL_PATTERN: &str = r"mul\((\d{1,3})\,(\d{1,3})\)";
+
+fn parse_input_regex(input: &str, matching_expr: &Regex) -> Vec<ParsedMatch> {
+  let line_starts = tokenizer::line_starts(input);
+  let mut toggles: Vec<(usize, bool)> = input
+    .match_indices("do()")
+    .map(|(idx, _)| (idx, true))
+    .chain(input.match_indices("don't()").map(|(idx, _)| (idx, false)))
+    .collect();
+  toggles.sort();
+
+  matching_expr
+    .find_iter(input)
+    .map(|m| {
+      // ## the enable state is set by the last toggle before the match
+      let toggles_before = toggles.partition_point(|&(idx, _)| idx < m.start());
+      ParsedMatch {
+        content: m.as_str().to_string(),
+        span: Span::locate(m.start(), m.len(), &line_starts),
+        enabled: toggles_before == 0 || toggles[toggles_before - 1].1,
+      }
+    })
+    .collect()
 }
 
-fn parse_input_walkthrough(input: &str) -> Vec<String> {
+fn parse_input_walkthrough(input: &str) -> Vec<ParsedMatch> {
   let mut parser = parser::Parser::new();
   input.chars().for_each(|c| {
     parser.consume(c);
   });
 
-  parser.get_parsed_input().to_vec()
+  parser.get_matches().to_vec()
 }
 
 fn multiply(mul_expr: &str, capture_regex: &Regex) -> usize {
@@ -24,14 +54,14 @@ fn multiply(mul_expr: &str, capture_regex: &Regex) -> usize {
   a * b
 }
 
-fn calculate_sum(parsed_input: &Vec<String>, capture_regex: &Regex) -> usize {
+fn calculate_sum(parsed_input: &Vec<ParsedMatch>, capture_regex: &Regex) -> usize {
   parsed_input
     .iter()
-    .fold(0, |acc, mul_expr| acc + multiply(mul_expr, &capture_regex) as usize)
+    .fold(0, |acc, parsed| acc + multiply(&parsed.content, &capture_regex) as usize)
 }
 
 fn part1_regex_sum(input: &str) -> usize {
-  let capture_regex = Regex::new(r"mul\((\d+)\,(\d+)\)").unwrap();
+  let capture_regex = Regex::new(MUL_PATTERN).unwrap();
   capture_regex
     .captures_iter(input)
     .map(|capture| {
@@ -45,7 +75,7 @@ fn part1_regex_sum(input: &str) -> usize {
 }
 
 fn part2_regex_sum(input: &str) -> usize {
-  let capture_regex = Regex::new(r"do\(\)|don't\(\)|mul\((\d+)\,(\d+)\)").unwrap();
+  let capture_regex = Regex::new(&format!(r"do\(\)|don't\(\)|{MUL_PATTERN}")).unwrap();
   let mut enabled = true;
 
   capture_regex
@@ -61,27 +91,97 @@ fn part2_regex_sum(input: &str) -> usize {
           enabled = false;
           0
         }
-        _ => {
+        _ if enabled => {
           let (a, b) = (
             capture.get(1).unwrap().as_str().parse::<usize>().unwrap(),
             capture.get(2).unwrap().as_str().parse::<usize>().unwrap(),
           );
           a * b
         }
+        _ => 0,
       }
     })
     .sum()
 }
 
 pub fn part1(input: &str) -> usize {
-  let matching_expr = Regex::new(r"mul\((\d+)\,(\d+)\)").unwrap();
-  let parsed_input: Vec<String> = parse_input_regex(input, &matching_expr);
+  let matching_expr = Regex::new(MUL_PATTERN).unwrap();
+  let parsed_input: Vec<ParsedMatch> = parse_input_regex(input, &matching_expr);
   calculate_sum(&parsed_input, &matching_expr)
 }
 
+// ## `do()`/`don't()` are handled by the interpreter in the same pass, no preprocessing involved
 pub fn part2(input: &str) -> usize {
-  let matching_expr = Regex::new(r"mul\((\d+)\,(\d+)\)").unwrap();
-  let preprocessed_input = preprocessor::preprocess(input);
-  let parsed_input: Vec<String> = parse_input_regex(&preprocessed_input, &matching_expr);
-  calculate_sum(&parsed_input, &matching_expr)
+  let result = interpreter::evaluate(input, tokenizer::Registry::standard()) as usize;
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::day3::tokenizer::Registry;
+
+  const EXAMPLE_1: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+  const EXAMPLE_2: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+  const TRICKY: &str = "mmul(1,2)mul(3,mul(4,5))mul( 6,7)mul(8,9dodon't(do()don'tmul(1,1)don't()mul(2,2)ddo()mul(3,3)";
+  const LONG_NUMBERS: &str = "mul(1234,1)mul(1,1234)mul(0123,4)mul(999,999)mul(12345)";
+  const NESTED: &str = "mul(mul(2,3))mul(4,mul(5,6)mulmul(7,8)mul(9,10mul(1,1)";
+  const TRAILING_DONT: &str = "mul(2,3)do()mul(4,5)don't(";
+  const SPLIT_BY_TOGGLES: &str = "mul(2don't()do(),3)mudon't()do()l(4,5)";
+
+  fn walkthrough_sum(input: &str) -> usize {
+    let matching_expr = Regex::new(MUL_PATTERN).unwrap();
+    calculate_sum(&parse_input_walkthrough(input), &matching_expr)
+  }
+
+  #[test]
+  fn test_part2_regex_sum() {
+    assert_eq!(part2_regex_sum(EXAMPLE_2), 48);
+    assert_eq!(part2_regex_sum(EXAMPLE_2), part2(EXAMPLE_2));
+    assert_eq!(part2_regex_sum("don't()mul(2,3)do()mul(4,5)don't()mul(6,7)"), 20);
+  }
+
+  #[test]
+  fn test_regex_and_walkthrough_locate_the_same_matches() {
+    let matching_expr = Regex::new(MUL_PATTERN).unwrap();
+    [EXAMPLE_1, EXAMPLE_2, TRICKY, LONG_NUMBERS, NESTED, TRAILING_DONT]
+      .into_iter()
+      .for_each(|input| {
+        assert_eq!(parse_input_regex(input, &matching_expr), parse_input_walkthrough(input), "{input}");
+      });
+
+    let enabled_sum: usize = parse_input_regex(EXAMPLE_2, &matching_expr)
+      .into_iter()
+      .filter(|parsed| parsed.enabled)
+      .map(|parsed| multiply(&parsed.content, &matching_expr))
+      .sum();
+    assert_eq!(enabled_sum, 48);
+  }
+
+  #[test]
+  fn test_byte_parser_cross_check() {
+    [EXAMPLE_1, EXAMPLE_2, TRICKY, LONG_NUMBERS, NESTED, TRAILING_DONT]
+      .into_iter()
+      .for_each(|input| {
+        let part1_sum = byte_parser::part1(input.as_bytes());
+        assert_eq!(part1_sum, part1(input), "{input}");
+        assert_eq!(part1_sum, part1_regex_sum(input), "{input}");
+        assert_eq!(part1_sum, walkthrough_sum(input), "{input}");
+        assert_eq!(part1_sum, interpreter::evaluate(input, Registry::mul_only()) as usize, "{input}");
+
+        let part2_sum = byte_parser::part2(input.as_bytes());
+        assert_eq!(part2_sum, part2(input), "{input}");
+        assert_eq!(part2_sum, part2_regex_sum(input), "{input}");
+        assert_eq!(part2_sum, walkthrough_sum(&preprocessor::preprocess(input)), "{input}");
+        assert_eq!(part2_sum, interpreter::evaluate(input, Registry::standard()) as usize, "{input}");
+      });
+
+    assert_eq!(byte_parser::part1(EXAMPLE_1.as_bytes()), 161);
+    assert_eq!(byte_parser::part2(EXAMPLE_2.as_bytes()), 48);
+    assert_eq!(byte_parser::part2(TRICKY.as_bytes()), 2 + 20 + 1 + 9);
+    assert_eq!(byte_parser::part1(LONG_NUMBERS.as_bytes()), 999 * 999);
+    assert_eq!(byte_parser::part1(NESTED.as_bytes()), 6 + 30 + 56 + 1);
+    assert_eq!(byte_parser::part2(TRAILING_DONT.as_bytes()), 6 + 20);
+    assert_eq!(part2(SPLIT_BY_TOGGLES), 0);
+  }
 }
@@ -1,20 +1,27 @@
 use crate::util::io::{bench_spacer, Env};
 
 use super::*;
+use crate::day3::byte_parser;
+use crate::day3::interpreter;
+use crate::day3::tokenizer::Registry;
 
 #[derive(Debug)]
 enum BenchImpl {
   Part1Walkthrough,
   Part1RegexParse,
   Part1RegexCapture,
+  Part1Interpreter,
+  Part1Bytes,
   Part2Walkthrough,
   Part2RegexParse,
   Part2RegexCapture,
   Part2RegexPreprocessAndCapture,
+  Part2Interpreter,
+  Part2Bytes,
 }
 
 fn run_benchmark(input: &str, fn_impl: BenchImpl) {
-  let matching_expr = Regex::new(r"mul\((\d+)\,(\d+)\)").unwrap();
+  let matching_expr = Regex::new(MUL_PATTERN).unwrap();
 
   let now = std::time::Instant::now();
   let result = match fn_impl {
@@ -32,6 +39,14 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
       let result = calculate_sum(&parsed_input, &matching_expr);
       result
     }
+    BenchImpl::Part1Interpreter => {
+      let result = interpreter::evaluate(input, Registry::mul_only()) as usize;
+      result
+    }
+    BenchImpl::Part1Bytes => {
+      let result = byte_parser::part1(input.as_bytes());
+      result
+    }
     BenchImpl::Part2RegexParse => {
       let preprocessed_input = preprocessor::preprocess(input);
       let parsed_input = parse_input_regex(&preprocessed_input, &matching_expr);
@@ -41,7 +56,11 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
     BenchImpl::Part2RegexPreprocessAndCapture => {
       let preprocessed_input = preprocessor::preprocess(input);
       let parsed_input = parse_input_regex(&preprocessed_input, &matching_expr);
-      let parsed_input_2 = parsed_input.iter().map(|s| s.chars()).flatten().collect::<String>();
+      let parsed_input_2 = parsed_input
+        .iter()
+        .map(|s| s.content.chars())
+        .flatten()
+        .collect::<String>();
       let result = part1_regex_sum(&parsed_input_2);
       result
     }
@@ -49,6 +68,14 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
       let result = part2_regex_sum(&input);
       result
     }
+    BenchImpl::Part2Interpreter => {
+      let result = interpreter::evaluate(input, Registry::standard()) as usize;
+      result
+    }
+    BenchImpl::Part2Bytes => {
+      let result = byte_parser::part2(input.as_bytes());
+      result
+    }
     BenchImpl::Part2Walkthrough => {
       let preprocessed_input = preprocessor::preprocess(input);
       let parsed_input = parse_input_walkthrough(&preprocessed_input);
@@ -65,8 +92,12 @@ pub fn run(input: &str, env: Env) {
   run_benchmark(input, BenchImpl::Part1Walkthrough);
   run_benchmark(input, BenchImpl::Part1RegexParse);
   run_benchmark(input, BenchImpl::Part1RegexCapture);
+  run_benchmark(input, BenchImpl::Part1Interpreter);
+  run_benchmark(input, BenchImpl::Part1Bytes);
   run_benchmark(input, BenchImpl::Part2Walkthrough);
   run_benchmark(input, BenchImpl::Part2RegexParse);
   run_benchmark(input, BenchImpl::Part2RegexCapture);
   run_benchmark(input, BenchImpl::Part2RegexPreprocessAndCapture);
+  run_benchmark(input, BenchImpl::Part2Interpreter);
+  run_benchmark(input, BenchImpl::Part2Bytes);
 }
@@ -0,0 +1,133 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Keyword {
+  Mul,
+  Do,
+  Dont,
+}
+
+impl Keyword {
+  fn text(&self) -> &'static [u8] {
+    match self {
+      Keyword::Mul => b"mul(",
+      Keyword::Do => b"do()",
+      Keyword::Dont => b"don't()",
+    }
+  }
+}
+
+const MAX_DIGITS: u8 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ByteState {
+  Start,
+  Keyword { keyword: Keyword, matched: usize },
+  FirstInt { value: usize, digits: u8 },
+  SecondInt { first: usize, value: usize, digits: u8 },
+}
+
+// Enum state machine over bytes: the whole state is a few integers, so nothing is allocated or boxed per byte
+#[derive(Debug, Clone, Copy)]
+pub struct ByteParser {
+  state: ByteState,
+  with_switches: bool,
+  enabled: bool,
+  sum: usize,
+}
+
+impl ByteParser {
+  pub fn new(with_switches: bool) -> Self {
+    Self {
+      state: ByteState::Start,
+      with_switches,
+      enabled: true,
+      sum: 0,
+    }
+  }
+
+  pub fn sum(&self) -> usize {
+    self.sum
+  }
+
+  fn start(&self, c: u8) -> ByteState {
+    let keyword = match c {
+      b'm' => Keyword::Mul,
+      b'd' if self.with_switches => Keyword::Do,
+      _ => return ByteState::Start,
+    };
+    ByteState::Keyword { keyword, matched: 1 }
+  }
+
+  fn match_keyword(&mut self, keyword: Keyword, matched: usize, c: u8) -> Option<ByteState> {
+    // ## `do` and `don't` share their first two bytes
+    let keyword = match (keyword, matched, c) {
+      (Keyword::Do, 2, b'n') => Keyword::Dont,
+      _ => keyword,
+    };
+    if keyword.text()[matched] != c {
+      return None;
+    }
+
+    let matched = matched + 1;
+    if matched < keyword.text().len() {
+      return Some(ByteState::Keyword { keyword, matched });
+    }
+    match keyword {
+      Keyword::Mul => return Some(ByteState::FirstInt { value: 0, digits: 0 }),
+      Keyword::Do => self.enabled = true,
+      Keyword::Dont => self.enabled = false,
+    }
+    Some(ByteState::Start)
+  }
+
+  fn next_state(&mut self, c: u8) -> Option<ByteState> {
+    match (self.state, c) {
+      (ByteState::Start, _) => Some(self.start(c)),
+      (ByteState::Keyword { keyword, matched }, _) => self.match_keyword(keyword, matched, c),
+      (ByteState::FirstInt { value, digits }, b'0'..=b'9') if digits < MAX_DIGITS => Some(ByteState::FirstInt {
+        value: value * 10 + (c - b'0') as usize,
+        digits: digits + 1,
+      }),
+      (ByteState::FirstInt { value, digits }, b',') if digits > 0 => Some(ByteState::SecondInt {
+        first: value,
+        value: 0,
+        digits: 0,
+      }),
+      (ByteState::SecondInt { first, value, digits }, b'0'..=b'9') if digits < MAX_DIGITS => {
+        Some(ByteState::SecondInt {
+          first,
+          value: value * 10 + (c - b'0') as usize,
+          digits: digits + 1,
+        })
+      }
+      (ByteState::SecondInt { first, value, digits }, b')') if digits > 0 => {
+        if self.enabled {
+          self.sum += first * value;
+        }
+        Some(ByteState::Start)
+      }
+      _ => None,
+    }
+  }
+
+  pub fn consume(&mut self, c: u8) {
+    self.state = match self.next_state(c) {
+      Some(state) => state,
+      // ## no keyword byte appears past the keywords' first byte, so a failed match can only restart at `c` itself
+      None => self.start(c),
+    };
+  }
+}
+
+pub fn sum_instructions(input: &[u8], with_switches: bool) -> usize {
+  let mut parser = ByteParser::new(with_switches);
+  input.iter().for_each(|&c| parser.consume(c));
+  parser.sum()
+}
+
+pub fn part1(input: &[u8]) -> usize {
+  sum_instructions(input, false)
+}
+
+pub fn part2(input: &[u8]) -> usize {
+  sum_instructions(input, true)
+}
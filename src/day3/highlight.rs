@@ -0,0 +1,128 @@
+use regex::Regex;
+
+use crate::day3::parser::ParsedMatch;
+use crate::day3::{parse_input_regex, parse_input_walkthrough, MUL_PATTERN};
+use crate::util::render::Rgb;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Region {
+  Ignored,
+  Enabled,
+  Disabled,
+  Mismatch,
+}
+
+impl Region {
+  fn color(&self) -> Option<Rgb> {
+    match self {
+      Region::Ignored => None,
+      Region::Enabled => Some(Rgb(0, 200, 64)),
+      Region::Disabled => Some(Rgb(200, 160, 0)),
+      Region::Mismatch => Some(Rgb(220, 32, 32)),
+    }
+  }
+}
+
+fn mark_regions(regions: &mut Vec<Region>, matches: &[ParsedMatch]) {
+  matches.iter().for_each(|e| {
+    let region = match e.enabled {
+      true => Region::Enabled,
+      false => Region::Disabled,
+    };
+    regions[e.span.offset..e.span.offset + e.span.len].fill(region);
+  });
+}
+
+// Per input byte region; bytes matched by only one of the two parsers, or with a different enable state, are mismatches
+pub fn classify_regions(input: &str, matches: &[ParsedMatch], reference: &[ParsedMatch]) -> Vec<Region> {
+  let mut regions = vec![Region::Ignored; input.len()];
+  mark_regions(&mut regions, matches);
+  let mut reference_regions = vec![Region::Ignored; input.len()];
+  mark_regions(&mut reference_regions, reference);
+
+  regions
+    .iter_mut()
+    .zip(reference_regions)
+    .filter(|(region, reference_region)| **region != *reference_region)
+    .for_each(|(region, _)| *region = Region::Mismatch);
+  regions
+}
+
+pub fn to_ansi(input: &str, regions: &Vec<Region>) -> String {
+  let mut output = String::new();
+  let mut current: Option<Region> = None;
+  input.char_indices().for_each(|(offset, c)| {
+    let region = regions[offset];
+    if current != Some(region) {
+      output.push_str(&match region.color() {
+        Some(Rgb(r, g, b)) => format!("\x1b[0m\x1b[38;2;{r};{g};{b}m"),
+        // ## ignored input is dimmed
+        None => String::from("\x1b[0m\x1b[2m"),
+      });
+      current = Some(region);
+    }
+    output.push(c);
+  });
+  output.push_str("\x1b[0m\n");
+  output
+}
+
+// Matched instructions coloured by enable state, with red marking where the walkthrough parser and the regex disagree
+pub fn highlight_implementations(input: &str) -> String {
+  let matching_expr = Regex::new(MUL_PATTERN).unwrap();
+  let walkthrough = parse_input_walkthrough(input);
+  let reference = parse_input_regex(input, &matching_expr);
+  to_ansi(input, &classify_regions(input, &walkthrough, &reference))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+  fn parse_both(input: &str) -> (Vec<ParsedMatch>, Vec<ParsedMatch>) {
+    let matching_expr = Regex::new(MUL_PATTERN).unwrap();
+    (parse_input_walkthrough(input), parse_input_regex(input, &matching_expr))
+  }
+
+  #[test]
+  fn test_parsers_agree() {
+    let (walkthrough, reference) = parse_both(EXAMPLE);
+    assert_eq!(walkthrough, reference);
+
+    let regions = classify_regions(EXAMPLE, &walkthrough, &reference);
+    assert!(!regions.contains(&Region::Mismatch));
+    assert_eq!(regions[0], Region::Ignored);
+    assert_eq!(regions[1], Region::Enabled);
+    assert_eq!(regions[28], Region::Disabled);
+  }
+
+  #[test]
+  fn test_mismatch() {
+    // ## both parsers reject the over-long number, so a mismatch has to be staged by dropping a match
+    let input = "mul(1,2)\nmul(1234,1)mul(3,4)";
+    let (walkthrough, mut reference) = parse_both(input);
+    assert_eq!(walkthrough, reference);
+    assert_eq!(reference[1].span.line, 2);
+    assert_eq!(reference[1].span.column, 12);
+
+    reference.pop();
+    let regions = classify_regions(input, &walkthrough, &reference);
+    assert_eq!(regions[..8], [Region::Enabled; 8]);
+    assert_eq!(regions[9..20], [Region::Ignored; 11]);
+    assert_eq!(regions[20..], [Region::Mismatch; 8]);
+    assert!(to_ansi(input, &regions).contains("\x1b[38;2;220;32;32mmul(3,4)"));
+  }
+
+  #[test]
+  fn test_enable_state_mismatch() {
+    let input = "don't()mul(1,2)";
+    let (walkthrough, mut reference) = parse_both(input);
+    reference[0].enabled = true;
+
+    let regions = classify_regions(input, &walkthrough, &reference);
+    assert_eq!(regions[..7], [Region::Ignored; 7]);
+    assert_eq!(regions[7..], [Region::Mismatch; 8]);
+  }
+}
@@ -0,0 +1,118 @@
+use crate::day3::tokenizer::{Effect, Registry, Span, Tokenizer};
+
+// An instruction as found in the input, along with whether the machine was enabled when reaching it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Executed {
+  pub name: String,
+  pub args: Vec<i64>,
+  pub span: Span,
+  pub enabled: bool,
+}
+
+// Single pass evaluation: the machine tokenizes its input and executes every instruction right away, `do`/`don't`
+// switching whether the accumulating instructions count
+#[derive(Debug, Clone)]
+pub struct Machine {
+  tokenizer: Tokenizer,
+  enabled: bool,
+  sum: i64,
+}
+
+impl Machine {
+  pub fn new(registry: Registry) -> Self {
+    Self {
+      tokenizer: Tokenizer::new(registry),
+      enabled: true,
+      sum: 0,
+    }
+  }
+
+  pub fn sum(&self) -> i64 {
+    self.sum
+  }
+
+  pub fn is_enabled(&self) -> bool {
+    self.enabled
+  }
+
+  pub fn feed(&mut self, chunk: &[u8]) -> Vec<Executed> {
+    let tokens = self.tokenizer.feed(chunk);
+    tokens
+      .into_iter()
+      .map(|token| {
+        let spec = self.tokenizer.registry().spec(token.instruction);
+        let executed = Executed {
+          name: spec.name.clone(),
+          args: token.args,
+          span: token.span,
+          enabled: self.enabled,
+        };
+        match spec.effect {
+          Effect::Accumulate(eval) => {
+            if self.enabled {
+              self.sum += eval(&executed.args);
+            }
+          }
+          Effect::Enable => self.enabled = true,
+          Effect::Disable => self.enabled = false,
+        }
+        executed
+      })
+      .collect()
+  }
+}
+
+pub fn trace(input: &str, registry: Registry) -> Vec<Executed> {
+  let mut machine = Machine::new(registry);
+  machine.feed(input.as_bytes())
+}
+
+pub fn evaluate(input: &str, registry: Registry) -> i64 {
+  let mut machine = Machine::new(registry);
+  machine.feed(input.as_bytes());
+  machine.sum()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE_1: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+  const EXAMPLE_2: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+  #[test]
+  fn test_examples() {
+    assert_eq!(evaluate(EXAMPLE_1, Registry::mul_only()), 161);
+    assert_eq!(evaluate(EXAMPLE_2, Registry::mul_only()), 161);
+    assert_eq!(evaluate(EXAMPLE_2, Registry::standard()), 48);
+  }
+
+  #[test]
+  fn test_trace() {
+    let executed = trace(EXAMPLE_2, Registry::standard());
+    let summary: Vec<(&str, usize, bool)> = executed
+      .iter()
+      .map(|e| (e.name.as_str(), e.span.offset, e.enabled))
+      .collect();
+    assert_eq!(
+      summary,
+      vec![
+        ("mul", 1, true),
+        ("don't", 20, true),
+        ("mul", 28, false),
+        ("mul", 48, false),
+        ("do", 59, false),
+        ("mul", 64, true),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_user_instructions() {
+    let registry = Registry::standard()
+      .with("add", 2, Effect::Accumulate(|args| args[0] + args[1]))
+      .with("sub", 2, Effect::Accumulate(|args| args[0] - args[1]));
+    let input = "mul(2,3)add(1,1)sub(1,10)don't()add(5,5)do()sub(3,1)";
+    assert_eq!(evaluate(input, registry), 6 + 2 - 9 + 2);
+  }
+}
@@ -1,3 +1,5 @@
+use crate::day3::tokenizer::Span;
+
 pub trait State {
   fn is_final_state(&self) -> bool {
     false
@@ -153,7 +155,7 @@ impl State for MulState {
         true => self.transition(c),
         false => self,
       },
-      false => self.reset(),
+      false => self.reset().consume(c),
     }
   }
 }
@@ -168,7 +170,7 @@ impl State for OpenParenState {
 
     match c {
       '(' => self.transition(c),
-      _ => self.reset(),
+      _ => self.reset().consume(c),
     }
   }
 }
@@ -194,9 +196,9 @@ impl State for FirstIntState {
       }
       ',' => match verify_state_content(&self.state_content) {
         true => self.transition(c),
-        false => self.reset(),
+        false => self.reset().consume(c),
       },
-      _ => self.reset(),
+      _ => self.reset().consume(c),
     }
   }
 }
@@ -211,7 +213,7 @@ impl State for CommaState {
 
     match c {
       ',' => self.transition(c),
-      _ => self.reset(),
+      _ => self.reset().consume(c),
     }
   }
 }
@@ -237,9 +239,9 @@ impl State for SecondIntState {
       }
       ')' => match verify_state_content(&self.state_content) {
         true => self.transition(c),
-        false => self.reset(),
+        false => self.reset().consume(c),
       },
-      _ => self.reset(),
+      _ => self.reset().consume(c),
     }
   }
 }
@@ -250,7 +252,7 @@ impl State for CloseParenState {
 
     match c {
       ')' => self.transition(c),
-      _ => self.reset(),
+      _ => self.reset().consume(c),
     }
   }
 
@@ -279,16 +281,35 @@ impl State for MatchedState {
 
 // ======================================================================================
 
+// A `mul` instruction matched in the input, enabled unless the last `do()` or `don't()` before it was a `don't()`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedMatch {
+  pub content: String,
+  pub span: Span,
+  pub enabled: bool,
+}
+
 pub struct Parser {
   parsed_input: Vec<String>,
+  matches: Vec<ParsedMatch>,
   state: Option<Box<dyn State>>,
+  offset: usize,
+  line_starts: Vec<usize>,
+  // ## the last chars consumed, enough to recognize `do()` and `don't()`
+  recent: Vec<char>,
+  enabled: bool,
 }
 
 impl Parser {
   pub fn new() -> Self {
     Self {
       parsed_input: Vec::new(),
+      matches: Vec::new(),
       state: Some(Box::new(ResetState::new())),
+      offset: 0,
+      line_starts: vec![0],
+      recent: Vec::new(),
+      enabled: true,
     }
   }
 
@@ -296,16 +317,95 @@ impl Parser {
     &self.parsed_input
   }
 
+  pub fn get_matches(&self) -> &Vec<ParsedMatch> {
+    &self.matches
+  }
+
+  fn track_position(&mut self, c: char) {
+    self.offset += c.len_utf8();
+    if c == '\n' {
+      self.line_starts.push(self.offset);
+    }
+
+    self.recent.push(c);
+    if self.recent.len() > "don't()".len() {
+      self.recent.remove(0);
+    }
+    if self.recent.ends_with(&['d', 'o', '(', ')']) {
+      self.enabled = true;
+    } else if self.recent.ends_with(&['d', 'o', 'n', '\'', 't', '(', ')']) {
+      self.enabled = false;
+    }
+  }
+
   pub fn consume(&mut self, c: char) {
+    self.track_position(c);
+
     if let Some(s) = self.state.take() {
       let next_state = s.consume(c);
 
       if next_state.is_final_state() {
         let matched_content = next_state.get_matched_content();
         self.parsed_input.push(matched_content.to_string());
+        self.matches.push(ParsedMatch {
+          content: matched_content.to_string(),
+          span: Span::locate(self.offset - matched_content.len(), matched_content.len(), &self.line_starts),
+          enabled: self.enabled,
+        });
       }
 
       self.state = Some(next_state);
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(input: &str) -> Vec<String> {
+    let mut parser = Parser::new();
+    input.chars().for_each(|c| parser.consume(c));
+    parser.get_parsed_input().to_vec()
+  }
+
+  #[test]
+  fn test_restarts_on_breaking_char() {
+    // ## the char breaking a partial match may start the next instruction
+    assert_eq!(parse("mmul(1,2)"), vec!["mul(1,2)"]);
+    assert_eq!(parse("mul(3,mul(4,5))"), vec!["mul(4,5)"]);
+    assert_eq!(parse("mul(6mul(7,8)"), vec!["mul(7,8)"]);
+    assert_eq!(parse("mul(1,2)mul( 6,7)"), vec!["mul(1,2)"]);
+  }
+
+  #[test]
+  fn test_matches_are_located() {
+    let mut parser = Parser::new();
+    "do()mul(1,2)\ndon't()xmul(3,4)".chars().for_each(|c| parser.consume(c));
+
+    let matches = parser.get_matches();
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].content, "mul(1,2)");
+    assert_eq!(
+      matches[0].span,
+      Span {
+        offset: 4,
+        len: 8,
+        line: 1,
+        column: 5
+      }
+    );
+    assert!(matches[0].enabled);
+    assert_eq!(matches[1].content, "mul(3,4)");
+    assert_eq!(
+      matches[1].span,
+      Span {
+        offset: 21,
+        len: 8,
+        line: 2,
+        column: 9
+      }
+    );
+    assert!(!matches[1].enabled);
+  }
+}
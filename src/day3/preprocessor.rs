@@ -48,5 +48,6 @@ pub fn preprocess(input: &str) -> String {
     result.push(&input[start_indices[i]..end_indices[i]]);
   }
 
-  return result.into_iter().collect();
+  // ## a separator keeps the tail of one segment from completing an instruction started in the previous one
+  return result.join("\n");
 }
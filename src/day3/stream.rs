@@ -0,0 +1,93 @@
+use std::io::{self, Read};
+
+use crate::day3::interpreter::Machine;
+use crate::day3::tokenizer::Registry;
+
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+// Reads the input chunk by chunk, yielding the sum after every chunk; the machine keeps the partially read instruction
+// and the enable state, so instructions split between chunks are still recognized
+pub struct RunningSums<R: Read> {
+  reader: R,
+  machine: Machine,
+  buffer: Vec<u8>,
+  finished: bool,
+}
+
+impl<R: Read> RunningSums<R> {
+  pub fn new(reader: R, registry: Registry, chunk_size: usize) -> Self {
+    assert!(chunk_size > 0, "CRITICAL: chunks can't be empty");
+    Self {
+      reader,
+      machine: Machine::new(registry),
+      buffer: vec![0; chunk_size],
+      finished: false,
+    }
+  }
+
+  pub fn machine(&self) -> &Machine {
+    &self.machine
+  }
+}
+
+impl<R: Read> Iterator for RunningSums<R> {
+  type Item = io::Result<i64>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.finished {
+      return None;
+    }
+    loop {
+      match self.reader.read(&mut self.buffer) {
+        Ok(0) => {
+          self.finished = true;
+          return None;
+        }
+        Ok(read) => {
+          self.machine.feed(&self.buffer[..read]);
+          return Some(Ok(self.machine.sum()));
+        }
+        Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+        Err(err) => {
+          self.finished = true;
+          return Some(Err(err));
+        }
+      }
+    }
+  }
+}
+
+pub fn evaluate_reader<R: Read>(reader: R, registry: Registry) -> io::Result<i64> {
+  RunningSums::new(reader, registry, DEFAULT_CHUNK_SIZE).try_fold(0, |_, sum| sum)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+  #[test]
+  fn test_any_chunk_size() {
+    (1..=EXAMPLE.len()).for_each(|chunk_size| {
+      let sums = RunningSums::new(EXAMPLE.as_bytes(), Registry::standard(), chunk_size);
+      let sums: Vec<i64> = sums.map(|sum| sum.unwrap()).collect();
+      assert_eq!(sums.len(), EXAMPLE.len().div_ceil(chunk_size));
+      assert_eq!(sums.last(), Some(&48), "chunk size {chunk_size}");
+    });
+  }
+
+  #[test]
+  fn test_running_sums() {
+    // ## chunks: `xmul(2,4)&mul[3,7]!^don'`, `t()_mul(5,5)+mul(32,64](m`, `ul(11,8)undo()?mul(8,5))`
+    let mut sums = RunningSums::new(EXAMPLE.as_bytes(), Registry::standard(), 25);
+    assert_eq!(sums.next().unwrap().unwrap(), 8);
+    assert!(sums.machine().is_enabled());
+    assert_eq!(sums.next().unwrap().unwrap(), 8);
+    assert!(!sums.machine().is_enabled());
+    assert_eq!(sums.next().unwrap().unwrap(), 48);
+    assert!(sums.next().is_none());
+
+    assert_eq!(evaluate_reader(EXAMPLE.as_bytes(), Registry::mul_only()).unwrap(), 161);
+  }
+}
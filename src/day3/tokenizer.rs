@@ -0,0 +1,287 @@
+// Instruction language of the corrupted memory: `name(arg,...)`, every argument being a 1-3 digit number.
+// A byte tokenizer of its own rather than an extension of `parser::State`: that machine hard-codes `mul`'s shape in
+// one type per state, while here the registry decides the names and arities at runtime
+
+#[derive(Debug, Clone, Copy)]
+pub enum Effect {
+  Accumulate(fn(&[i64]) -> i64),
+  Enable,
+  Disable,
+}
+
+#[derive(Debug, Clone)]
+pub struct InstructionSpec {
+  pub name: String,
+  pub arity: usize,
+  pub effect: Effect,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+  specs: Vec<InstructionSpec>,
+}
+
+impl Registry {
+  pub fn new() -> Self {
+    Self { specs: vec![] }
+  }
+
+  pub fn with(mut self, name: &str, arity: usize, effect: Effect) -> Self {
+    assert!(!name.is_empty(), "CRITICAL: instruction without a name");
+    self.specs.push(InstructionSpec {
+      name: name.to_string(),
+      arity,
+      effect,
+    });
+    self
+  }
+
+  // `mul` only, as in part 1
+  pub fn mul_only() -> Self {
+    Registry::new().with("mul", 2, Effect::Accumulate(|args| args[0] * args[1]))
+  }
+
+  // `mul` with the `do()`/`don't()` switches, as in part 2
+  pub fn standard() -> Self {
+    Registry::mul_only()
+      .with("do", 0, Effect::Enable)
+      .with("don't", 0, Effect::Disable)
+  }
+
+  pub fn spec(&self, instruction: usize) -> &InstructionSpec {
+    &self.specs[instruction]
+  }
+}
+
+// Line and column are 1-based, the column counting bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+  pub offset: usize,
+  pub len: usize,
+  pub line: usize,
+  pub column: usize,
+}
+
+impl Span {
+  // Span of `len` bytes at `offset`, given the offsets the input lines start at
+  pub fn locate(offset: usize, len: usize, line_starts: &[usize]) -> Self {
+    let line = line_starts.partition_point(|&start| start <= offset);
+    Self {
+      offset,
+      len,
+      line,
+      column: offset - line_starts[line - 1] + 1,
+    }
+  }
+}
+
+// Offsets the lines of `input` start at, the first one being 0
+pub fn line_starts(input: &str) -> Vec<usize> {
+  std::iter::once(0)
+    .chain(input.match_indices('\n').map(|(idx, _)| idx + 1))
+    .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Position {
+  offset: usize,
+  line: usize,
+  column: usize,
+}
+
+impl Position {
+  fn start() -> Self {
+    Self {
+      offset: 0,
+      line: 1,
+      column: 1,
+    }
+  }
+
+  fn advance(&mut self, c: u8) {
+    self.offset += 1;
+    match c {
+      b'\n' => {
+        self.line += 1;
+        self.column = 1;
+      }
+      _ => self.column += 1,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+  pub instruction: usize,
+  pub args: Vec<i64>,
+  pub span: Span,
+}
+
+#[derive(Debug, PartialEq)]
+enum MatchStatus {
+  Fail,
+  Prefix,
+  Complete,
+}
+
+const MAX_ARG_DIGITS: usize = 3;
+
+fn match_instruction(spec: &InstructionSpec, candidate: &[u8]) -> MatchStatus {
+  let name = spec.name.as_bytes();
+  if candidate.len() <= name.len() {
+    return match name.starts_with(candidate) {
+      true => MatchStatus::Prefix,
+      false => MatchStatus::Fail,
+    };
+  }
+  if !candidate.starts_with(name) || candidate[name.len()] != b'(' {
+    return MatchStatus::Fail;
+  }
+
+  let mut args_count = 0;
+  let mut digits = 0;
+  for &c in &candidate[name.len() + 1..] {
+    match c {
+      b'0'..=b'9' if digits < MAX_ARG_DIGITS && args_count < spec.arity => digits += 1,
+      b',' if digits > 0 && args_count + 1 < spec.arity => {
+        args_count += 1;
+        digits = 0;
+      }
+      b')' if (spec.arity == 0 && digits == 0) || (digits > 0 && args_count + 1 == spec.arity) => {
+        return MatchStatus::Complete;
+      }
+      _ => return MatchStatus::Fail,
+    }
+  }
+  MatchStatus::Prefix
+}
+
+fn parse_args(candidate: &[u8], name_len: usize) -> Vec<i64> {
+  let args = &candidate[name_len + 1..candidate.len() - 1];
+  args
+    .split(|&c| c == b',')
+    .filter(|arg| !arg.is_empty())
+    .map(|arg| arg.iter().fold(0, |acc, d| acc * 10 + (d - b'0') as i64))
+    .collect()
+}
+
+// Byte by byte tokenizer keeping the not yet decided candidate between calls, so the input may come in chunks; when
+// the candidate stops being a prefix of any instruction, its first byte is dropped and the rest re-examined
+#[derive(Debug, Clone)]
+pub struct Tokenizer {
+  registry: Registry,
+  candidate: Vec<u8>,
+  candidate_start: Position,
+}
+
+impl Tokenizer {
+  pub fn new(registry: Registry) -> Self {
+    Self {
+      registry,
+      candidate: vec![],
+      candidate_start: Position::start(),
+    }
+  }
+
+  pub fn registry(&self) -> &Registry {
+    &self.registry
+  }
+
+  fn examine_candidate(&self) -> (MatchStatus, Option<usize>) {
+    let mut status = MatchStatus::Fail;
+    for (instruction, spec) in self.registry.specs.iter().enumerate() {
+      match match_instruction(spec, &self.candidate) {
+        MatchStatus::Complete => return (MatchStatus::Complete, Some(instruction)),
+        MatchStatus::Prefix => status = MatchStatus::Prefix,
+        MatchStatus::Fail => {}
+      }
+    }
+    (status, None)
+  }
+
+  pub fn consume(&mut self, c: u8) -> Option<Token> {
+    self.candidate.push(c);
+    loop {
+      match self.examine_candidate() {
+        (MatchStatus::Complete, Some(instruction)) => {
+          let name_len = self.registry.specs[instruction].name.len();
+          let args = parse_args(&self.candidate, name_len);
+          let span = Span {
+            offset: self.candidate_start.offset,
+            len: self.candidate.len(),
+            line: self.candidate_start.line,
+            column: self.candidate_start.column,
+          };
+          self.candidate.drain(..).for_each(|c| self.candidate_start.advance(c));
+          return Some(Token {
+            instruction,
+            args,
+            span,
+          });
+        }
+        (MatchStatus::Prefix, _) => return None,
+        _ => {
+          let dropped = self.candidate.remove(0);
+          self.candidate_start.advance(dropped);
+          if self.candidate.is_empty() {
+            return None;
+          }
+        }
+      }
+    }
+  }
+
+  pub fn feed(&mut self, chunk: &[u8]) -> Vec<Token> {
+    chunk.iter().filter_map(|&c| self.consume(c)).collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_tokenize() {
+    let mut tokenizer = Tokenizer::new(Registry::standard());
+    let tokens = tokenizer.feed(b"mmul(2,4)mul(1234,1)don't()do_do()mul(1,2mul(3,4)");
+    let instructions: Vec<(usize, Vec<i64>)> = tokens.iter().map(|t| (t.instruction, t.args.clone())).collect();
+    assert_eq!(instructions, vec![(0, vec![2, 4]), (2, vec![]), (1, vec![]), (0, vec![3, 4])]);
+    let offsets: Vec<(usize, usize)> = tokens.iter().map(|t| (t.span.offset, t.span.len)).collect();
+    assert_eq!(offsets, vec![(1, 8), (20, 7), (30, 4), (41, 8)]);
+  }
+
+  #[test]
+  fn test_spans() {
+    let mut tokenizer = Tokenizer::new(Registry::mul_only());
+    let tokens = tokenizer.feed(b"mul(1,1)\nx\n  mul(2,2)");
+    let spans: Vec<Span> = tokens.into_iter().map(|t| t.span).collect();
+    assert_eq!(
+      spans,
+      vec![
+        Span {
+          offset: 0,
+          len: 8,
+          line: 1,
+          column: 1
+        },
+        Span {
+          offset: 13,
+          len: 8,
+          line: 3,
+          column: 3
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn test_arities() {
+    let registry = Registry::new()
+      .with("neg", 1, Effect::Accumulate(|args| -args[0]))
+      .with("sum3", 3, Effect::Accumulate(|args| args.iter().sum()));
+    let mut tokenizer = Tokenizer::new(registry);
+    let tokens = tokenizer.feed(b"neg()neg(7)sum3(1,2)sum3(1,2,3)sum3(1,2,3,4)");
+    let args: Vec<Vec<i64>> = tokens.into_iter().map(|t| t.args).collect();
+    assert_eq!(args, vec![vec![7], vec![1, 2, 3]]);
+  }
+}
@@ -1,4 +1,6 @@
 pub mod benchmarks;
+pub mod shape;
+pub mod word_search;
 
 const LOOKUP_XMAS: &str = "XMAS";
 const ALL_DIRECTION_LOOKUP_DIFFS: [(i16, i16); 8] =
@@ -82,6 +84,10 @@ fn count_xmas_index(input: &Vec<Vec<char>>) -> usize {
   return xmas_count;
 }
 
+fn count_xmas_word_search(input: &Vec<Vec<char>>) -> usize {
+  word_search::WordSearch::new(&[LOOKUP_XMAS]).count(input)
+}
+
 fn count_xmas(input: &Vec<Vec<char>>) -> usize {
   count_xmas_index(input)
 }
@@ -124,6 +130,13 @@ fn count_cross_mas(input: &Vec<Vec<char>>) -> usize {
   return xmas_count;
 }
 
+const SHAPE_CROSS_MAS: &str = "M?S\n?A?\nM?S";
+
+fn count_cross_mas_shape(input: &Vec<Vec<char>>) -> usize {
+  let cross_mas = shape::Shape::parse(SHAPE_CROSS_MAS);
+  shape::ShapeMatcher::new(cross_mas, shape::Symmetry::Rotations).count(input)
+}
+
 fn parse_input(input: &str) -> Vec<Vec<char>> {
   let parsed_input = input
     .split("\n")
@@ -144,3 +157,35 @@ pub fn part2(input: &str) -> usize {
   let parsed_input = parse_input(input);
   count_cross_mas(&parsed_input)
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+";
+
+  #[test]
+  fn test_part1() {
+    let parsed_input = parse_input(EXAMPLE);
+    assert_eq!(part1(EXAMPLE), 18);
+    assert_eq!(count_xmas_iter(&parsed_input), 18);
+    assert_eq!(count_xmas_word_search(&parsed_input), 18);
+  }
+
+  #[test]
+  fn test_part2() {
+    let parsed_input = parse_input(EXAMPLE);
+    assert_eq!(part2(EXAMPLE), 9);
+    assert_eq!(count_cross_mas_shape(&parsed_input), 9);
+  }
+}
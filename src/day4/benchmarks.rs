@@ -5,7 +5,9 @@ use crate::util::io::{bench_spacer, Env};
 enum BenchImpl {
   Part1Index,
   Part1Iter,
+  Part1WordSearch,
   Part2Std,
+  Part2Shape,
 }
 
 fn run_benchmark(input: &str, fn_impl: BenchImpl) {
@@ -21,11 +23,21 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
       let result = count_xmas_iter(&parsed_input);
       result
     }
+    BenchImpl::Part1WordSearch => {
+      let parsed_input = parse_input(input);
+      let result = count_xmas_word_search(&parsed_input);
+      result
+    }
     BenchImpl::Part2Std => {
       let parsed_input = parse_input(input);
       let result = count_cross_mas(&parsed_input);
       result
     }
+    BenchImpl::Part2Shape => {
+      let parsed_input = parse_input(input);
+      let result = count_cross_mas_shape(&parsed_input);
+      result
+    }
   };
   let elapsed = now.elapsed();
   println!("[{fn_impl:#?}] Elapsed {elapsed:.2?}; Result: {result}");
@@ -35,5 +47,7 @@ pub fn run(input: &str, env: Env) {
   bench_spacer(env);
   run_benchmark(input, BenchImpl::Part1Index);
   run_benchmark(input, BenchImpl::Part1Iter);
+  run_benchmark(input, BenchImpl::Part1WordSearch);
   run_benchmark(input, BenchImpl::Part2Std);
+  run_benchmark(input, BenchImpl::Part2Shape);
 }
@@ -0,0 +1,186 @@
+use itertools::Itertools;
+
+use super::word_search::WILDCARD;
+
+// A small rectangular grid of letters, `None` standing for a wildcard cell matching any letter
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shape {
+  cells: Vec<Vec<Option<char>>>,
+}
+
+impl Shape {
+  // Rows of letters, one per line, `?` for wildcards, e.g. "M?S\n?A?\nM?S"
+  pub fn parse(pattern: &str) -> Self {
+    let cells: Vec<Vec<Option<char>>> = pattern
+      .lines()
+      .map(|l| l.trim())
+      .filter(|l| !l.is_empty())
+      .map(|l| l.chars().map(|c| (c != WILDCARD).then_some(c)).collect())
+      .collect();
+    assert!(!cells.is_empty(), "CRITICAL: empty shape");
+    assert!(cells.iter().all(|row| row.len() == cells[0].len()), "CRITICAL: shape rows must all have the same length");
+    Self { cells }
+  }
+
+  pub fn height(&self) -> usize {
+    self.cells.len()
+  }
+
+  pub fn width(&self) -> usize {
+    self.cells[0].len()
+  }
+
+  // Quarter turn, clockwise
+  pub fn rotate(&self) -> Self {
+    let (height, width) = (self.height(), self.width());
+    let cells = (0..width)
+      .map(|i| (0..height).map(|j| self.cells[height - 1 - j][i]).collect())
+      .collect();
+    Self { cells }
+  }
+
+  // Mirror image, left to right
+  pub fn reflect(&self) -> Self {
+    let cells = self
+      .cells
+      .iter()
+      .map(|row| row.iter().rev().copied().collect())
+      .collect();
+    Self { cells }
+  }
+
+  fn matches_at(&self, grid: &Vec<Vec<char>>, top: usize, left: usize) -> bool {
+    self.cells.iter().enumerate().all(|(i, row)| {
+      row
+        .iter()
+        .enumerate()
+        .all(|(j, cell)| cell.is_none_or(|c| grid[top + i][left + j] == c))
+    })
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Symmetry {
+  Exact,
+  Rotations,
+  RotationsAndReflections,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ShapeMatch {
+  pub variant: usize,
+  pub top_left: (usize, usize),
+}
+
+// Looks up a shape along with its rotations and/or reflections, each distinct variant being matched on its own: a
+// symmetric shape (e.g. the X-MAS one under a half turn) is only counted once per location
+#[derive(Debug)]
+pub struct ShapeMatcher {
+  variants: Vec<Shape>,
+}
+
+impl ShapeMatcher {
+  pub fn new(shape: Shape, symmetry: Symmetry) -> Self {
+    let rotations = |shape: Shape| {
+      std::iter::successors(Some(shape), |s| Some(s.rotate()))
+        .take(4)
+        .collect::<Vec<Shape>>()
+    };
+    let variants: Vec<Shape> = match symmetry {
+      Symmetry::Exact => vec![shape],
+      Symmetry::Rotations => rotations(shape),
+      Symmetry::RotationsAndReflections => {
+        let reflected = shape.reflect();
+        rotations(shape).into_iter().chain(rotations(reflected)).collect()
+      }
+    };
+    Self {
+      variants: variants.into_iter().unique().collect(),
+    }
+  }
+
+  pub fn variants(&self) -> &Vec<Shape> {
+    &self.variants
+  }
+
+  pub fn find_all(&self, grid: &Vec<Vec<char>>) -> Vec<ShapeMatch> {
+    let (rows, cols) = match grid.first() {
+      Some(row) => (grid.len(), row.len()),
+      None => return vec![],
+    };
+
+    let mut matches: Vec<ShapeMatch> = vec![];
+    self.variants.iter().enumerate().for_each(|(variant, shape)| {
+      if shape.height() > rows || shape.width() > cols {
+        return;
+      }
+      for top in 0..=(rows - shape.height()) {
+        for left in 0..=(cols - shape.width()) {
+          if shape.matches_at(grid, top, left) {
+            matches.push(ShapeMatch {
+              variant,
+              top_left: (top, left),
+            });
+          }
+        }
+      }
+    });
+    matches.sort_by_key(|m| (m.top_left, m.variant));
+    matches
+  }
+
+  pub fn count(&self, grid: &Vec<Vec<char>>) -> usize {
+    self.find_all(grid).len()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn to_grid(input: &str) -> Vec<Vec<char>> {
+    input.lines().map(|l| l.chars().collect()).collect()
+  }
+
+  #[test]
+  fn test_transformations() {
+    let shape = Shape::parse("AB?\nCDE");
+    assert_eq!(shape.rotate(), Shape::parse("CA\nDB\nE?"));
+    assert_eq!(shape.reflect(), Shape::parse("?BA\nEDC"));
+    assert_eq!(shape.rotate().rotate().rotate().rotate(), shape);
+
+    let count_variants =
+      |pattern: &str, symmetry: Symmetry| ShapeMatcher::new(Shape::parse(pattern), symmetry).variants().len();
+    assert_eq!(count_variants("AB?\nCDE", Symmetry::RotationsAndReflections), 8);
+    assert_eq!(count_variants("M?S\n?A?\nM?S", Symmetry::Rotations), 4);
+    assert_eq!(count_variants("M?S\n?A?\nM?S", Symmetry::RotationsAndReflections), 4);
+    assert_eq!(count_variants("A?\n?A", Symmetry::RotationsAndReflections), 2);
+  }
+
+  #[test]
+  fn test_find_all() {
+    let grid = to_grid("ABAB\nBABA\nXYZA");
+    let matcher = ShapeMatcher::new(Shape::parse("A?\n?A"), Symmetry::Exact);
+    assert_eq!(
+      matcher.find_all(&grid),
+      vec![
+        ShapeMatch {
+          variant: 0,
+          top_left: (0, 0)
+        },
+        ShapeMatch {
+          variant: 0,
+          top_left: (0, 2)
+        },
+      ]
+    );
+
+    // ## only the transposed shape, i.e. a reflection, fits
+    let grid = to_grid("XY\nAZ");
+    let count = |symmetry: Symmetry| ShapeMatcher::new(Shape::parse("X?\nYZ"), symmetry).count(&grid);
+    assert_eq!(count(Symmetry::Exact), 0);
+    assert_eq!(count(Symmetry::Rotations), 0);
+    assert_eq!(count(Symmetry::RotationsAndReflections), 1);
+    assert_eq!(ShapeMatcher::new(Shape::parse("ABABA"), Symmetry::Rotations).count(&grid), 0);
+  }
+}
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+
+use crate::util::aho_corasick::AhoCorasick;
+
+pub const WILDCARD: char = '?';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+  N,
+  NE,
+  E,
+  SE,
+  S,
+  SW,
+  W,
+  NW,
+}
+
+impl Direction {
+  pub fn diff(&self) -> (isize, isize) {
+    match self {
+      Direction::N => (-1, 0),
+      Direction::NE => (-1, 1),
+      Direction::E => (0, 1),
+      Direction::SE => (1, 1),
+      Direction::S => (1, 0),
+      Direction::SW => (1, -1),
+      Direction::W => (0, -1),
+      Direction::NW => (-1, -1),
+    }
+  }
+
+  fn opposite(&self) -> Direction {
+    match self {
+      Direction::N => Direction::S,
+      Direction::NE => Direction::SW,
+      Direction::E => Direction::W,
+      Direction::SE => Direction::NW,
+      Direction::S => Direction::N,
+      Direction::SW => Direction::NE,
+      Direction::W => Direction::E,
+      Direction::NW => Direction::SE,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Occurrence {
+  pub word: usize,
+  pub start: (usize, usize),
+  pub direction: Direction,
+}
+
+// A word as read along a grid line, either as written or reversed (i.e. read in the opposite direction)
+#[derive(Debug)]
+struct Pattern {
+  word: usize,
+  is_reversed: bool,
+  len: usize,
+  segments_count: usize,
+}
+
+// Wildcard-free run of a pattern, at `offset` from the pattern's start
+#[derive(Debug)]
+struct Segment {
+  pattern: usize,
+  offset: usize,
+}
+
+// A grid line: the cells along it, read in the `direction` order
+struct Line {
+  cells: Vec<(usize, usize)>,
+  direction: Direction,
+}
+
+fn get_lines(rows: usize, cols: usize) -> Vec<Line> {
+  let mut lines: Vec<Line> = vec![];
+  let collect_line = |start: (usize, usize), direction: Direction| {
+    let (di, dj) = direction.diff();
+    let mut cells = vec![];
+    let (mut i, mut j) = (start.0 as isize, start.1 as isize);
+    while i >= 0 && j >= 0 && (i as usize) < rows && (j as usize) < cols {
+      cells.push((i as usize, j as usize));
+      (i, j) = (i + di, j + dj);
+    }
+    Line { cells, direction }
+  };
+
+  (0..rows).for_each(|i| lines.push(collect_line((i, 0), Direction::E)));
+  (0..cols).for_each(|j| lines.push(collect_line((0, j), Direction::S)));
+  // ## diagonals start on the first row or, below it, on the first (SE) or last (SW) column
+  (0..cols).for_each(|j| lines.push(collect_line((0, j), Direction::SE)));
+  (1..rows).for_each(|i| lines.push(collect_line((i, 0), Direction::SE)));
+  (0..cols).for_each(|j| lines.push(collect_line((0, j), Direction::SW)));
+  (1..rows).for_each(|i| lines.push(collect_line((i, cols - 1), Direction::SW)));
+  lines
+}
+
+// All the words in all 8 directions at once: rows, columns and both diagonals are scanned forward only, the 4 other
+// directions being covered by also looking for the reversed words. Wildcards split a pattern into segments and a
+// pattern matches where all of its segments do, at their offsets.
+#[derive(Debug)]
+pub struct WordSearch {
+  patterns: Vec<Pattern>,
+  segments: Vec<Segment>,
+  automaton: AhoCorasick,
+}
+
+impl WordSearch {
+  pub fn new(words: &[&str]) -> Self {
+    let mut patterns: Vec<Pattern> = vec![];
+    let mut segments: Vec<Segment> = vec![];
+    let mut segment_texts: Vec<Vec<char>> = vec![];
+
+    words.iter().enumerate().for_each(|(word, w)| {
+      let chars: Vec<char> = w.chars().collect();
+      assert!(!chars.is_empty(), "CRITICAL: empty word to look up");
+      [false, true].into_iter().for_each(|is_reversed| {
+        let pattern_chars: Vec<char> = match is_reversed {
+          true => chars.iter().rev().copied().collect(),
+          false => chars.clone(),
+        };
+        let pattern = patterns.len();
+        let mut segments_count = 0;
+        let mut offset = 0;
+        pattern_chars.split(|&c| c == WILDCARD).for_each(|segment| {
+          if !segment.is_empty() {
+            segments.push(Segment { pattern, offset });
+            segment_texts.push(segment.to_vec());
+            segments_count += 1;
+          }
+          offset += segment.len() + 1;
+        });
+        patterns.push(Pattern {
+          word,
+          is_reversed,
+          len: pattern_chars.len(),
+          segments_count,
+        });
+      });
+    });
+
+    Self {
+      automaton: AhoCorasick::new(&segment_texts),
+      patterns,
+      segments,
+    }
+  }
+
+  fn to_occurrence(&self, pattern: &Pattern, line: &Line, start: usize) -> Occurrence {
+    match pattern.is_reversed {
+      false => Occurrence {
+        word: pattern.word,
+        start: line.cells[start],
+        direction: line.direction,
+      },
+      true => Occurrence {
+        word: pattern.word,
+        start: line.cells[start + pattern.len - 1],
+        direction: line.direction.opposite(),
+      },
+    }
+  }
+
+  fn find_in_line(&self, grid: &Vec<Vec<char>>, line: &Line, occurrences: &mut Vec<Occurrence>) {
+    let line_len = line.cells.len();
+    let fits = |pattern: &Pattern, start: usize| start + pattern.len <= line_len;
+
+    // ## matched segments count per (pattern, start)
+    let mut votes: HashMap<(usize, usize), usize> = HashMap::new();
+    let text = line.cells.iter().map(|&(i, j)| grid[i][j]);
+    self.automaton.for_each_match(text, |segment_idx, segment_start| {
+      let segment = &self.segments[segment_idx];
+      let pattern = &self.patterns[segment.pattern];
+      if segment_start < segment.offset || !fits(pattern, segment_start - segment.offset) {
+        return;
+      }
+      let start = segment_start - segment.offset;
+      let count = votes.entry((segment.pattern, start)).or_insert(0);
+      *count += 1;
+      if *count == pattern.segments_count {
+        occurrences.push(self.to_occurrence(pattern, line, start));
+      }
+    });
+
+    // ## words made of wildcards only match wherever they fit
+    self
+      .patterns
+      .iter()
+      .filter(|p| p.segments_count == 0)
+      .for_each(|pattern| {
+        (0..line_len)
+          .filter(|&start| fits(pattern, start))
+          .for_each(|start| occurrences.push(self.to_occurrence(pattern, line, start)));
+      });
+  }
+
+  pub fn find_all(&self, grid: &Vec<Vec<char>>) -> Vec<Occurrence> {
+    let mut occurrences: Vec<Occurrence> = vec![];
+    if grid.is_empty() || grid[0].is_empty() {
+      return occurrences;
+    }
+    get_lines(grid.len(), grid[0].len())
+      .iter()
+      .for_each(|line| self.find_in_line(grid, line, &mut occurrences));
+    occurrences.sort();
+    occurrences
+  }
+
+  pub fn count(&self, grid: &Vec<Vec<char>>) -> usize {
+    self.find_all(grid).len()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn to_grid(input: &str) -> Vec<Vec<char>> {
+    input.lines().map(|l| l.chars().collect()).collect()
+  }
+
+  #[test]
+  fn test_directions() {
+    let grid = to_grid("ABC\nDEF\nGHI");
+    let search = WordSearch::new(&["AEI", "FC", "HEB", "GEC", "I"]);
+    let occurrences = search.find_all(&grid);
+    assert_eq!(
+      occurrences,
+      vec![
+        Occurrence {
+          word: 0,
+          start: (0, 0),
+          direction: Direction::SE
+        },
+        Occurrence {
+          word: 1,
+          start: (1, 2),
+          direction: Direction::N
+        },
+        Occurrence {
+          word: 2,
+          start: (2, 1),
+          direction: Direction::N
+        },
+        Occurrence {
+          word: 3,
+          start: (2, 0),
+          direction: Direction::NE
+        },
+      ]
+      .into_iter()
+      .chain(
+        [Direction::N, Direction::NE, Direction::E, Direction::SE]
+          .into_iter()
+          .chain([Direction::S, Direction::SW, Direction::W, Direction::NW])
+          .map(|direction| Occurrence {
+            word: 4,
+            start: (2, 2),
+            direction
+          })
+      )
+      .collect::<Vec<Occurrence>>()
+    );
+  }
+
+  #[test]
+  fn test_wildcards() {
+    let grid = to_grid("ABCA\nXBYA");
+    let search = WordSearch::new(&["A?C", "B??", "??"]);
+    let occurrences = search.find_all(&grid);
+    let count_word = |word: usize| occurrences.iter().filter(|o| o.word == word).count();
+    assert_eq!(count_word(0), 1);
+    // ## only eastwards in both rows: columns and diagonals are too short, and no B has 2 cells to its west
+    assert_eq!(count_word(1), 2);
+    // ## every pair of adjacent cells, read both ways: 3 + 3 in rows, 4 in columns, 3 + 3 in diagonals
+    assert_eq!(count_word(2), 2 * (6 + 4 + 6));
+  }
+}
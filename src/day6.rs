@@ -1,4 +1,9 @@
 use std::collections::HashMap;
+use std::io;
+
+use crate::util::animation::{self, Recorder};
+use crate::util::bitset::{self, BitSet};
+use crate::util::render::{self, Rgb};
 
 pub mod benchmarks;
 
@@ -87,13 +92,27 @@ fn goes_out_of_bounds(i_count: usize, j_count: usize, i_lookup: i16, j_lookup: i
     || usize::try_from(j_lookup).ok().unwrap() >= j_count
 }
 
+fn render_guard_frame(matrix: &Vec<Vec<char>>, guard: &Guard) -> Vec<Vec<char>> {
+  let mut frame = matrix.clone();
+  frame[guard.pos.i][guard.pos.j] = MOVE_DIRECTIONS.iter().find(|md| md.dir == guard.dir).unwrap().marker;
+  frame
+}
+
 fn simulate_guard_movement(matrix: &mut Vec<Vec<char>>, guard: &mut Guard) {
+  simulate_guard_movement_recorded(matrix, guard, &mut Recorder::disabled());
+}
+
+fn simulate_guard_movement_recorded(matrix: &mut Vec<Vec<char>>, guard: &mut Guard, recorder: &mut Recorder<char>) {
   let (matrix_size_i, matrix_size_j) = (matrix.len(), matrix[0].len());
 
   let move_direction_diffs: HashMap<Dir, (i16, i16)> = MOVE_DIRECTIONS.map(|md| (md.dir, md.diff)).into();
   let move_direction_on_obstacle: HashMap<Dir, Dir> = MOVE_DIRECTIONS.map(|md| (md.dir, md.on_obstacle)).into();
 
+  let mut step = 0;
   loop {
+    recorder.capture(step, || render_guard_frame(matrix, guard));
+    step += 1;
+
     let (i, j) = (guard.pos.i, guard.pos.j);
     let look_ahead_diff = move_direction_diffs.get(&guard.dir).unwrap();
     let look_ahead_i = &i16::try_from(i).unwrap() + look_ahead_diff.0;
@@ -125,6 +144,49 @@ fn simulate_guard_movement(matrix: &mut Vec<Vec<char>>, guard: &mut Guard) {
       };
     }
   }
+  recorder.capture_last(step, || render_guard_frame(matrix, guard));
+}
+
+// Patrol keeping the visited cells in a bitset instead of marking them on the grid, `None` when the guard loops;
+// `obstacle` is an extra obstructed cell, so the grid doesn't need cloning
+fn patrol_visited_cells(matrix: &Vec<Vec<char>>, guard: &Guard, obstacle: Option<usize>) -> Option<BitSet> {
+  let (matrix_size_i, matrix_size_j) = (matrix.len(), matrix[0].len());
+  let dir_idx = |dir: Dir| MOVE_DIRECTIONS.iter().position(|md| md.dir == dir).unwrap();
+
+  let mut visited = BitSet::with_capacity(matrix_size_i * matrix_size_j);
+  // ## a loop is the guard getting back to a cell facing the same direction
+  let mut states = BitSet::with_capacity(matrix_size_i * matrix_size_j * MOVE_DIRECTIONS.len());
+  let (mut pos, mut dir) = (guard.pos, guard.dir);
+  loop {
+    let cell = bitset::encode_grid_cell(pos.i, pos.j, matrix_size_j);
+    visited.insert(cell);
+    if !states.insert(cell * MOVE_DIRECTIONS.len() + dir_idx(dir)) {
+      return None;
+    }
+
+    let move_direction = &MOVE_DIRECTIONS[dir_idx(dir)];
+    let look_ahead_i = i16::try_from(pos.i).unwrap() + move_direction.diff.0;
+    let look_ahead_j = i16::try_from(pos.j).unwrap() + move_direction.diff.1;
+    if goes_out_of_bounds(matrix_size_i, matrix_size_j, look_ahead_i, look_ahead_j) {
+      return Some(visited);
+    }
+
+    let (i, j) = (look_ahead_i as usize, look_ahead_j as usize);
+    match matrix[i][j] == '#' || obstacle == Some(bitset::encode_grid_cell(i, j, matrix_size_j)) {
+      true => dir = move_direction.on_obstacle,
+      false => pos = Pos { i, j },
+    }
+  }
+}
+
+fn calc_obstruction_count_bitset(matrix: &Vec<Vec<char>>, guard: &Guard) -> usize {
+  let initial_cell = bitset::encode_grid_cell(guard.pos.i, guard.pos.j, matrix[0].len());
+  patrol_visited_cells(matrix, guard, None)
+    .unwrap()
+    .iter()
+    .filter(|&cell| cell != initial_cell)
+    .filter(|&cell| patrol_visited_cells(matrix, guard, Some(cell)).is_none())
+    .count()
 }
 
 fn parse_input(input: &str) -> (Vec<Vec<char>>, Guard) {
@@ -185,6 +247,27 @@ fn calc_obstruction_count_clone(walked_matrix: &Vec<Vec<char>>, matrix: &Vec<Vec
     })
 }
 
+fn guard_palette() -> render::Palette<char> {
+  MOVE_DIRECTIONS
+    .iter()
+    .fold(render::char_map_palette(), |palette, md| palette.with(md.marker, Rgb(255, 64, 64)))
+    .with('X', Rgb(255, 160, 0))
+}
+
+pub fn dump_state(input: &str, output: &render::Output) -> io::Result<()> {
+  let (mut matrix, mut guard) = parse_input(input);
+  simulate_guard_movement(&mut matrix, &mut guard);
+  render::dump_chars(&matrix, &guard_palette(), output)
+}
+
+pub fn animate(input: &str, every_nth: usize, target: &animation::Target) -> io::Result<()> {
+  let (mut matrix, mut guard) = parse_input(input);
+  let palette = guard_palette();
+  let mut recorder = Recorder::for_target(every_nth, &palette, target)?;
+  simulate_guard_movement_recorded(&mut matrix, &mut guard, &mut recorder);
+  recorder.emit(&palette, |&c| c, target)
+}
+
 pub fn part1(input: &str) -> usize {
   let (mut matrix, mut guard_initial_position) = parse_input(input);
   simulate_guard_movement(&mut matrix, &mut guard_initial_position);
@@ -199,3 +282,68 @@ pub fn part2(input: &str) -> usize {
   let result = calc_obstruction_count_clone(&matrix, &initial_matrix, &initial_guard);
   result
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = "....#.....\n.........#\n..........\n..#.......\n.......#..\n\
+                         ..........\n.#..^.....\n........#.\n#.........\n......#...\n";
+
+  #[test]
+  fn test_visited_bitset() {
+    let (matrix, guard) = parse_input(EXAMPLE);
+    let visited = patrol_visited_cells(&matrix, &guard, None).unwrap();
+    assert_eq!(visited.count(), part1(EXAMPLE));
+    assert_eq!(visited.count(), 41);
+    assert!(visited.contains(bitset::encode_grid_cell(6, 4, 10)));
+
+    assert_eq!(calc_obstruction_count_bitset(&matrix, &guard), part2(EXAMPLE));
+    assert_eq!(calc_obstruction_count_bitset(&matrix, &guard), 6);
+    assert!(patrol_visited_cells(&matrix, &guard, Some(bitset::encode_grid_cell(6, 3, 10))).is_none());
+  }
+
+  #[test]
+  fn test_animate_from_args() {
+    let dir = std::env::temp_dir().join(format!("aoc-2024-day6-frames-{}", std::process::id()));
+    let args = [
+      String::from("--animate-day06"),
+      String::from("--animate-every=2"),
+      format!("--animate-ppm={}", dir.display()),
+      String::from("--animate-scale=1"),
+    ];
+    let (every_nth, target) = animation::parse_args(6, &args).unwrap();
+    animate("#..\n...\n.^.\n", every_nth, &target).unwrap();
+
+    let mut written: Vec<String> = std::fs::read_dir(&dir)
+      .unwrap()
+      .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+      .collect();
+    written.sort();
+    std::fs::remove_dir_all(&dir).unwrap();
+    // ## up two cells, then off the grid: steps 0 and 2 by stride, the last step always
+    assert_eq!(written, vec!["frame_000000.ppm", "frame_000002.ppm", "frame_000003.ppm"]);
+  }
+
+  #[test]
+  fn test_dump_state() {
+    let path = std::env::temp_dir().join(format!("aoc-2024-day6-{}.ppm", std::process::id()));
+    let output = render::Output::Ppm {
+      path: path.clone(),
+      scale: 1,
+    };
+    dump_state("#..\n...\n.^.\n", &output).unwrap();
+
+    let image = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let header = b"P6\n3 3\n255\n";
+    assert_eq!(&image[..header.len()], header);
+
+    // ## the guard walks straight up the middle column: obstacle, then walked cells
+    let pixel = |i: usize, j: usize| &image[header.len() + (i * 3 + j) * 3..][..3];
+    assert_eq!(pixel(0, 0), &[128, 128, 128]);
+    assert_eq!(pixel(0, 1), &[255, 160, 0]);
+    assert_eq!(pixel(2, 1), &[255, 160, 0]);
+    assert_eq!(pixel(2, 0), &[16, 16, 16]);
+  }
+}
@@ -4,8 +4,10 @@ use crate::util::io::{bench_spacer, Env};
 #[derive(Debug)]
 enum BenchImpl {
   Part1Std,
+  Part1VisitedBitSet,
   Part2InputParsing,
   Part2InputCloning,
+  Part2VisitedBitSet,
 }
 
 fn run_benchmark(input: &str, fn_impl: BenchImpl) {
@@ -17,6 +19,11 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
       let result = get_guard_distinct_positions_count(&matrix);
       result
     }
+    BenchImpl::Part1VisitedBitSet => {
+      let (matrix, guard) = parse_input(input);
+      let result = patrol_visited_cells(&matrix, &guard, None).unwrap().count();
+      result
+    }
     BenchImpl::Part2InputParsing => {
       let (mut matrix, mut guard_initial_position) = parse_input(input);
       simulate_guard_movement(&mut matrix, &mut guard_initial_position);
@@ -30,6 +37,11 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
       let result = calc_obstruction_count_clone(&matrix, &initial_matrix, &initial_guard);
       result
     }
+    BenchImpl::Part2VisitedBitSet => {
+      let (matrix, guard) = parse_input(input);
+      let result = calc_obstruction_count_bitset(&matrix, &guard);
+      result
+    }
   };
   let elapsed = now.elapsed();
   println!("[{fn_impl:#?}] Elapsed {elapsed:.2?}; Result: {result}");
@@ -38,6 +50,8 @@ fn run_benchmark(input: &str, fn_impl: BenchImpl) {
 pub fn run(input: &str, env: Env) {
   bench_spacer(env);
   run_benchmark(input, BenchImpl::Part1Std);
+  run_benchmark(input, BenchImpl::Part1VisitedBitSet);
   run_benchmark(input, BenchImpl::Part2InputParsing);
   run_benchmark(input, BenchImpl::Part2InputCloning);
+  run_benchmark(input, BenchImpl::Part2VisitedBitSet);
 }
@@ -1,8 +1,11 @@
 use std::collections::HashMap;
+use std::io;
 use std::ops::Range;
 
 use itertools::Itertools;
 
+use crate::util::render::{self, Rgb};
+
 pub mod benchmarks;
 
 #[derive(Debug, Copy, Clone, Default)]
@@ -100,6 +103,26 @@ fn parse_input(input: &str) -> (FieldSize, Vec<Antenna>) {
   (field_size, antennas)
 }
 
+fn render_antinodes(field_size: &FieldSize, antennas: &Vec<Antenna>, antinodes: &Vec<Pos>) -> Vec<Vec<char>> {
+  let mut grid = vec![vec!['.'; field_size.y]; field_size.x];
+  antinodes.iter().for_each(|an| grid[an.i][an.j] = '#');
+  antennas.iter().for_each(|a| grid[a.pos.i][a.pos.j] = a.freq_marker);
+  grid
+}
+
+pub fn dump_state(input: &str, output: &render::Output) -> io::Result<()> {
+  let (field_size, antennas) = parse_input(input);
+  let grouped_antennas = group_antennas(&antennas);
+  let antinode_dist_multipliers = 0..std::cmp::min(field_size.x, field_size.y);
+  let antinodes = calculate_resonant_freqs_antinodes(grouped_antennas, field_size, &antinode_dist_multipliers);
+
+  let grid = render_antinodes(&field_size, &antennas, &antinodes);
+  let palette = render::Palette::new()
+    .with('.', Rgb(16, 16, 16))
+    .with('#', Rgb(255, 220, 0));
+  render::dump_chars(&grid, &palette, output)
+}
+
 pub fn part1(input: &str) -> usize {
   let (field_size, antennas) = parse_input(input);
   let grouped_antennas = group_antennas(&antennas);
@@ -5,7 +5,13 @@ use aoc_2024::day12;
 use aoc_2024::day13;
 use aoc_2024::day14;
 use aoc_2024::day15;
+use aoc_2024::day16;
+use aoc_2024::day17;
+use aoc_2024::day18;
+use aoc_2024::day19;
 use aoc_2024::day2;
+use aoc_2024::day20;
+use aoc_2024::day21;
 use aoc_2024::day22;
 use aoc_2024::day23;
 use aoc_2024::day24;
@@ -17,13 +23,22 @@ use aoc_2024::day6;
 use aoc_2024::day7;
 use aoc_2024::day8;
 use aoc_2024::day9;
+use aoc_2024::util::animation;
 use aoc_2024::util::io;
+use aoc_2024::util::render;
+use std::path::PathBuf;
 
 fn main() {
   _day25();
   _day24();
   _day23();
   _day22();
+  _day21();
+  _day20();
+  _day19();
+  _day18();
+  _day17();
+  _day16();
   _day15();
   _day14();
   _day13();
@@ -46,16 +61,36 @@ fn day_init(day: u8) -> (String, String) {
   io::get_day_inputs(day)
 }
 
+// `--dump-state` prints the final grid of the days supporting it, `--dump-state-ppm` saves it as `dumps/dayNN.ppm`
+fn dump_state_output(day: u8) -> Option<render::Output> {
+  match std::env::args()
+    .find(|arg| arg == "--dump-state" || arg == "--dump-state-ppm")?
+    .as_str()
+  {
+    "--dump-state" => Some(render::Output::Terminal),
+    _ => Some(render::Output::Ppm {
+      path: PathBuf::from(format!("dumps/day{day:02}.ppm")),
+      scale: 4,
+    }),
+  }
+}
+
 fn _day1() {
   let day: u8 = 1;
   let (_test_input, input) = day_init(day);
 
-  let total_dist = day1::part1(&input);
-  let similarity_score = day1::part2(&input);
+  let total_dist = day1::part1(&input).unwrap();
+  let similarity_score = day1::part2(&input).unwrap();
 
   println!("[Day{day:02}::Part1] Total Distance => {}", total_dist);
   println!("[Day{day:02}::Part2] Similarity Score => {}", similarity_score);
 
+  #[cfg(feature = "bigint")]
+  {
+    println!("[Day{day:02}::Part1::BigInt] Total Distance => {}", day1::bigint::part1(&input).unwrap());
+    println!("[Day{day:02}::Part2::BigInt] Similarity Score => {}", day1::bigint::part2(&input).unwrap());
+  }
+
   day1::benchmarks::run(&input, io::Env::Run);
   day1::benchmarks::run(&_test_input, io::Env::Test);
 }
@@ -64,12 +99,26 @@ fn _day2() {
   let day: u8 = 2;
   let (_test_input, input) = day_init(day);
 
-  let safe_reports_count = day2::part1(&input);
-  let dampened_safe_reports_count = day2::part2(&input);
+  let safe_reports_count = day2::part1(&input).unwrap();
+  let dampened_safe_reports_count = day2::part2(&input).unwrap();
 
   println!("[Day{day:02}::Part1] Safe Reports Count => {safe_reports_count}");
   println!("[Day{day:02}::Part2] Dampened Safe Reports Count => {dampened_safe_reports_count}");
 
+  #[cfg(feature = "bigint")]
+  {
+    println!("[Day{day:02}::Part1::BigInt] Safe Reports Count => {}", day2::bigint::part1(&input).unwrap());
+    println!("[Day{day:02}::Part2::BigInt] Dampened Safe Reports Count => {}", day2::bigint::part2(&input).unwrap());
+  }
+
+  if std::env::args().any(|arg| arg == "--print-unsafe-reports") {
+    day2::diagnose_reports(&input, true, day2::StepBounds::default())
+      .unwrap()
+      .iter()
+      .filter(|d| !d.is_safe())
+      .for_each(|d| println!("[Day{day:02}::Unsafe] {d}"));
+  }
+
   day2::benchmarks::run(&input, io::Env::Run);
   day2::benchmarks::run(&_test_input, io::Env::Test);
 }
@@ -84,6 +133,10 @@ fn _day3() {
   println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
   println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
 
+  if std::env::args().any(|arg| arg == "--highlight-day3") {
+    print!("{}", day3::highlight::highlight_implementations(&input));
+  }
+
   day3::benchmarks::run(&input, io::Env::Run);
   day3::benchmarks::run(&_test_input, io::Env::Test);
 }
@@ -126,6 +179,14 @@ fn _day6() {
   println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
   println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
 
+  if let Some(output) = dump_state_output(day) {
+    day6::dump_state(&input, &output).unwrap();
+  }
+
+  if let Some((every_nth, target)) = animation::parse_args(day, std::env::args()) {
+    day6::animate(&input, every_nth, &target).unwrap();
+  }
+
   day6::benchmarks::run(&input, io::Env::Run);
   day6::benchmarks::run(&_test_input, io::Env::Test);
 }
@@ -154,6 +215,10 @@ fn _day8() {
   println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
   println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
 
+  if let Some(output) = dump_state_output(day) {
+    day8::dump_state(&input, &output).unwrap();
+  }
+
   day8::benchmarks::run(&input, io::Env::Run);
   day8::benchmarks::run(&_test_input, io::Env::Test);
 }
@@ -204,27 +269,36 @@ fn _day12() {
   let day: u8 = 12;
   let (_test_input, input) = day_init(day);
 
-  // let part1_result = day12::part1(&_test_input);
   let part1_result = day12::part1(&input);
-  // let part2_result = day12::part2(&input);
+  let part2_result = day12::part2(&input);
 
   println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
-  // println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
+  println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
+
+  if let Some(output) = dump_state_output(day) {
+    day12::dump_state(&input, &output).unwrap();
+  }
 
-  // day12::benchmarks::run(&_test_input, io::Env::Test);
-  // day12::benchmarks::run(&input, io::Env::Run);
+  day12::benchmarks::run(&_test_input, io::Env::Test);
+  day12::benchmarks::run(&input, io::Env::Run);
 }
 
 fn _day13() {
   let day: u8 = 13;
   let (_test_input, input) = day_init(day);
 
-  let part1_result = day13::part1(&input);
-  let part2_result = day13::part2(&input);
+  let part1_result = day13::part1(&input).unwrap();
+  let part2_result = day13::part2(&input).unwrap();
 
   println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
   println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
 
+  #[cfg(feature = "bigint")]
+  {
+    println!("[Day{day:02}::Part1::BigInt] Part 1 Result => {}", day13::bigint::part1(&input));
+    println!("[Day{day:02}::Part2::BigInt] Part 2 Result => {}", day13::bigint::part2(&input));
+  }
+
   day13::benchmarks::run(&_test_input, io::Env::Test);
   day13::benchmarks::run(&input, io::Env::Run);
 }
@@ -239,6 +313,15 @@ fn _day14() {
   println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
   println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
 
+  // ## the state after part 2's moves is the easter egg picture
+  if let Some(output) = dump_state_output(day) {
+    day14::dump_state(&input, day14::Matrix { x: 101, y: 103 }, part2_result, &output).unwrap();
+  }
+
+  if let Some((every_nth, target)) = animation::parse_args(day, std::env::args()) {
+    day14::animate(&input, day14::Matrix { x: 101, y: 103 }, part2_result, every_nth, &target).unwrap();
+  }
+
   day14::benchmarks::run(&_test_input, day14::Matrix { x: 11, y: 7 }, io::Env::Test);
   day14::benchmarks::run(&input, day14::Matrix { x: 101, y: 103 }, io::Env::Run);
 }
@@ -248,15 +331,110 @@ fn _day15() {
   let (_test_input, input) = day_init(day);
 
   let part1_result = day15::part1(&input);
-  // let part2_result = day15::part2(&input);
+  let part2_result = day15::part2(&input);
 
   println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
-  // println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
+  println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
+
+  if let Some(output) = dump_state_output(day) {
+    day15::dump_state(&input, &output).unwrap();
+  }
+
+  if let Some((every_nth, target)) = animation::parse_args(day, std::env::args()) {
+    day15::animate(&input, every_nth, &target).unwrap();
+  }
 
   day15::benchmarks::run(&_test_input, io::Env::Test);
   day15::benchmarks::run(&input, io::Env::Run);
 }
 
+fn _day16() {
+  let day: u8 = 16;
+  let (_test_input, input) = day_init(day);
+
+  let part1_result = day16::part1(&input);
+  let part2_result = day16::part2(&input);
+
+  println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
+  println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
+
+  day16::benchmarks::run(&_test_input, io::Env::Test);
+  day16::benchmarks::run(&input, io::Env::Run);
+}
+
+fn _day17() {
+  let day: u8 = 17;
+  let (_test_input, input) = day_init(day);
+
+  let part1_result = day17::part1(&input);
+  let part2_result = day17::part2(&input).unwrap();
+
+  println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
+  println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
+  println!("[Day{day:02}::Disassembly]");
+  day17::disassemble_input(&input).iter().for_each(|l| println!("  {l}"));
+
+  day17::benchmarks::run(&_test_input, io::Env::Test);
+  day17::benchmarks::run(&input, io::Env::Run);
+}
+
+fn _day18() {
+  let day: u8 = 18;
+  let (_test_input, input) = day_init(day);
+
+  let grid_size = day18::Matrix { x: 71, y: 71 };
+  let part1_result = day18::part1(&input, grid_size, 1024);
+  let part2_result = day18::part2(&input, grid_size);
+
+  println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
+  println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
+
+  day18::benchmarks::run(&_test_input, day18::Matrix { x: 7, y: 7 }, 12, io::Env::Test);
+  day18::benchmarks::run(&input, grid_size, 1024, io::Env::Run);
+}
+
+fn _day19() {
+  let day: u8 = 19;
+  let (_test_input, input) = day_init(day);
+
+  let part1_result = day19::part1(&input);
+  let part2_result = day19::part2(&input);
+
+  println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
+  println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
+
+  day19::benchmarks::run(&_test_input, io::Env::Test);
+  day19::benchmarks::run(&input, io::Env::Run);
+}
+
+fn _day20() {
+  let day: u8 = 20;
+  let (_test_input, input) = day_init(day);
+
+  let part1_result = day20::part1(&input);
+  let part2_result = day20::part2(&input);
+
+  println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
+  println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
+
+  day20::benchmarks::run(&_test_input, 50, io::Env::Test);
+  day20::benchmarks::run(&input, 100, io::Env::Run);
+}
+
+fn _day21() {
+  let day: u8 = 21;
+  let (_test_input, input) = day_init(day);
+
+  let part1_result = day21::part1(&input);
+  let part2_result = day21::part2(&input);
+
+  println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
+  println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
+
+  day21::benchmarks::run(&_test_input, io::Env::Test);
+  day21::benchmarks::run(&input, io::Env::Run);
+}
+
 fn _day22() {
   let day: u8 = 22;
   let (_test_input, input) = day_init(day);
@@ -290,8 +468,12 @@ fn _day24() {
   let (_test_input, input) = day_init(day);
 
   let part1_result = day24::part1(&input);
+  let part2_result = day24::part2(&input).unwrap();
+  let adder_report = day24::analyze_adder(&input).unwrap();
 
   println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
+  println!("[Day{day:02}::Part2] Part 2 Result => {part2_result}");
+  println!("[Day{day:02}::Part2] Faulty Bits => {:?}", adder_report.faulty_bits);
 
   day24::benchmarks::run(&_test_input, io::Env::Test);
   day24::benchmarks::run(&input, io::Env::Run);
@@ -301,9 +483,11 @@ fn _day25() {
   let day: u8 = 25;
   let (_test_input, input) = day_init(day);
 
-  let part1_result = day25::part1(&input);
+  let part1_result = day25::part1(&input).unwrap();
+  let part2_result = day25::part2(&input).unwrap();
 
   println!("[Day{day:02}::Part1] Part 1 Result => {part1_result}");
+  println!("[Day{day:02}::Part2] Part 2 Result =>\n{part2_result}");
 
   day25::benchmarks::run(&_test_input, io::Env::Test);
   day25::benchmarks::run(&input, io::Env::Run);
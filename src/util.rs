@@ -1 +1,7 @@
+pub mod aho_corasick;
+pub mod animation;
+pub mod bitset;
+pub mod checked;
+pub mod dsu;
 pub mod io;
+pub mod render;
@@ -0,0 +1,132 @@
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+  children: HashMap<char, usize>,
+  fail: usize,
+  // ## patterns ending here, including the ones reachable through the fail links
+  outputs: Vec<usize>,
+}
+
+// Multi-pattern matcher: a trie of the patterns with fail links, finding all (overlapping) occurrences of every
+// pattern in a single pass over the text
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+  nodes: Vec<Node>,
+  pattern_lens: Vec<usize>,
+}
+
+impl AhoCorasick {
+  pub fn new(patterns: &[Vec<char>]) -> Self {
+    let mut automaton = Self {
+      nodes: vec![Node::default()],
+      pattern_lens: patterns.iter().map(|p| p.len()).collect(),
+    };
+    patterns
+      .iter()
+      .enumerate()
+      .for_each(|(idx, p)| automaton.insert(idx, p));
+    automaton.build_fail_links();
+    automaton
+  }
+
+  fn insert(&mut self, pattern_idx: usize, pattern: &[char]) {
+    assert!(!pattern.is_empty(), "CRITICAL: empty patterns match everywhere");
+    let mut node = 0;
+    for &c in pattern {
+      node = match self.nodes[node].children.get(&c) {
+        Some(&child) => child,
+        None => {
+          self.nodes.push(Node::default());
+          let child = self.nodes.len() - 1;
+          self.nodes[node].children.insert(c, child);
+          child
+        }
+      };
+    }
+    self.nodes[node].outputs.push(pattern_idx);
+  }
+
+  fn build_fail_links(&mut self) {
+    let mut queue: VecDeque<usize> = self.nodes[0].children.values().copied().collect();
+    while let Some(node) = queue.pop_front() {
+      let children: Vec<(char, usize)> = self.nodes[node].children.iter().map(|(&c, &n)| (c, n)).collect();
+      for (c, child) in children {
+        let mut fail = self.nodes[node].fail;
+        while fail != 0 && !self.nodes[fail].children.contains_key(&c) {
+          fail = self.nodes[fail].fail;
+        }
+        let child_fail = match self.nodes[fail].children.get(&c) {
+          Some(&n) if n != child => n,
+          _ => 0,
+        };
+        self.nodes[child].fail = child_fail;
+        let inherited = self.nodes[child_fail].outputs.clone();
+        self.nodes[child].outputs.extend(inherited);
+        queue.push_back(child);
+      }
+    }
+  }
+
+  fn step(&self, mut node: usize, c: char) -> usize {
+    loop {
+      if let Some(&next) = self.nodes[node].children.get(&c) {
+        return next;
+      }
+      if node == 0 {
+        return 0;
+      }
+      node = self.nodes[node].fail;
+    }
+  }
+
+  // Calls `on_match(pattern index, start position)` for every occurrence, in the order the occurrences end
+  pub fn for_each_match<I, F>(&self, text: I, mut on_match: F)
+  where
+    I: IntoIterator<Item = char>,
+    F: FnMut(usize, usize),
+  {
+    let mut node = 0;
+    text.into_iter().enumerate().for_each(|(pos, c)| {
+      node = self.step(node, c);
+      self.nodes[node]
+        .outputs
+        .iter()
+        .for_each(|&p| on_match(p, pos + 1 - self.pattern_lens[p]));
+    });
+  }
+
+  pub fn find_all<I>(&self, text: I) -> Vec<(usize, usize)>
+  where
+    I: IntoIterator<Item = char>,
+  {
+    let mut matches: Vec<(usize, usize)> = vec![];
+    self.for_each_match(text, |pattern, start| matches.push((pattern, start)));
+    matches
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn to_patterns(patterns: &[&str]) -> Vec<Vec<char>> {
+    patterns.iter().map(|p| p.chars().collect()).collect()
+  }
+
+  #[test]
+  fn test_find_all() {
+    let automaton = AhoCorasick::new(&to_patterns(&["he", "she", "his", "hers"]));
+    let mut matches = automaton.find_all("ushers".chars());
+    matches.sort();
+    assert_eq!(matches, vec![(0, 2), (1, 1), (3, 2)]);
+  }
+
+  #[test]
+  fn test_overlapping_and_duplicates() {
+    let automaton = AhoCorasick::new(&to_patterns(&["aa", "a", "aa"]));
+    let mut matches = automaton.find_all("aaa".chars());
+    matches.sort();
+    assert_eq!(matches, vec![(0, 0), (0, 1), (1, 0), (1, 1), (1, 2), (2, 0), (2, 1)]);
+  }
+}
@@ -0,0 +1,523 @@
+use std::fs;
+use std::hash::Hash;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+use crate::util::render::{self, Palette};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame<T> {
+  pub step: usize,
+  pub grid: Vec<Vec<T>>,
+}
+
+// Frame-recording hook for simulations: keeps every Nth step, building the grid only for kept steps. Frames are
+// either kept in memory (for the terminal replay) or written to disk as soon as they are recorded
+#[derive(Debug)]
+pub struct Recorder<T> {
+  every_nth: Option<usize>,
+  last_step: Option<usize>,
+  sink: Sink<T>,
+}
+
+#[derive(Debug)]
+enum Sink<T> {
+  Memory(Vec<Frame<T>>),
+  PpmFrames {
+    dir: PathBuf,
+    palette: Palette<T>,
+    scale: usize,
+    // ## the first write error, once set no more frames are written
+    error: Option<io::Error>,
+  },
+}
+
+impl<T: Hash + Eq + Clone> Recorder<T> {
+  pub fn new(every_nth: usize) -> Self {
+    assert!(every_nth > 0, "CRITICAL: frames can't be recorded every 0th step");
+    Self {
+      every_nth: Some(every_nth),
+      last_step: None,
+      sink: Sink::Memory(vec![]),
+    }
+  }
+
+  pub fn disabled() -> Self {
+    Self {
+      every_nth: None,
+      last_step: None,
+      sink: Sink::Memory(vec![]),
+    }
+  }
+
+  pub fn writing_ppm_frames(every_nth: usize, dir: &Path, palette: &Palette<T>, scale: usize) -> io::Result<Self> {
+    fs::create_dir_all(dir)?;
+    Ok(Self {
+      sink: Sink::PpmFrames {
+        dir: dir.to_path_buf(),
+        palette: palette.clone(),
+        scale,
+        error: None,
+      },
+      ..Self::new(every_nth)
+    })
+  }
+
+  pub fn for_target(every_nth: usize, palette: &Palette<T>, target: &Target) -> io::Result<Self> {
+    match target {
+      Target::PpmFrames { dir, scale } => Self::writing_ppm_frames(every_nth, dir, palette, *scale),
+      Target::Terminal { .. } => Ok(Self::new(every_nth)),
+    }
+  }
+
+  fn record(&mut self, step: usize, grid: Vec<Vec<T>>) {
+    self.last_step = Some(step);
+    match &mut self.sink {
+      Sink::Memory(frames) => frames.push(Frame { step, grid }),
+      Sink::PpmFrames {
+        dir,
+        palette,
+        scale,
+        error,
+      } => {
+        if error.is_none() {
+          let path = dir.join(format!("frame_{step:06}.ppm"));
+          if let Err(e) = fs::write(path, render::to_ppm(&grid, palette, *scale)) {
+            *error = Some(e);
+          }
+        }
+      }
+    }
+  }
+
+  pub fn capture<F>(&mut self, step: usize, render_grid: F)
+  where
+    F: FnOnce() -> Vec<Vec<T>>,
+  {
+    if let Some(every_nth) = self.every_nth {
+      if step.is_multiple_of(every_nth) {
+        self.record(step, render_grid());
+      }
+    }
+  }
+
+  // Always keeps the final state, even when its step isn't a multiple of N
+  pub fn capture_last<F>(&mut self, step: usize, render_grid: F)
+  where
+    F: FnOnce() -> Vec<Vec<T>>,
+  {
+    if self.every_nth.is_some() && self.last_step != Some(step) {
+      self.record(step, render_grid());
+    }
+  }
+
+  // Frames kept in memory, none when they are written to disk
+  pub fn frames(&self) -> &[Frame<T>] {
+    match &self.sink {
+      Sink::Memory(frames) => frames,
+      Sink::PpmFrames { .. } => &[],
+    }
+  }
+
+  pub fn save_ppm_frames(&self, dir: &Path, palette: &Palette<T>, scale: usize) -> io::Result<()> {
+    save_ppm_frames(self.frames(), dir, palette, scale)
+  }
+
+  pub fn emit<G>(self, palette: &Palette<T>, glyph: G, target: &Target) -> io::Result<()>
+  where
+    G: Fn(&T) -> char,
+  {
+    let frames = match self.sink {
+      // ## already on disk, only the write error (if any) is left to report
+      Sink::PpmFrames { error, .. } => return error.map_or(Ok(()), Err),
+      Sink::Memory(frames) => frames,
+    };
+    match target {
+      Target::PpmFrames { dir, scale } => save_ppm_frames(&frames, dir, palette, *scale),
+      Target::Terminal { delay } => {
+        let commands = stdin_controls().lock().unwrap();
+        // ## drop whatever was typed while no player was running
+        commands.try_iter().for_each(drop);
+        let mut player = Player::new(&frames, *delay);
+        player.run(palette, glyph, &commands, &mut io::stdout())
+      }
+    }
+  }
+}
+
+fn save_ppm_frames<T: Hash + Eq>(
+  frames: &[Frame<T>],
+  dir: &Path,
+  palette: &Palette<T>,
+  scale: usize,
+) -> io::Result<()> {
+  fs::create_dir_all(dir)?;
+  frames.iter().try_for_each(|frame| {
+    let path = dir.join(format!("frame_{:06}.ppm", frame.step));
+    fs::write(path, render::to_ppm(&frame.grid, palette, scale))
+  })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+  PpmFrames { dir: PathBuf, scale: usize },
+  Terminal { delay: Duration },
+}
+
+// Command line options of an animated day: `--animate-dayNN` turns it on, recording every `--animate-every=N`th step
+// (1 by default) and playing the frames in the terminal `--animate-delay=MS` apart (100 by default), or with
+// `--animate-ppm[=DIR]` writing them to DIR (`dumps/dayNN/` by default) scaled by `--animate-scale=N` (4 by default)
+pub fn parse_args<I, S>(day: u8, args: I) -> Option<(usize, Target)>
+where
+  I: IntoIterator<Item = S>,
+  S: AsRef<str>,
+{
+  let args: Vec<S> = args.into_iter().collect();
+  let value = |name: &str| {
+    args.iter().find_map(|arg| match arg.as_ref().split_once('=') {
+      Some((key, value)) if key == name => Some(value.to_string()),
+      _ if arg.as_ref() == name => Some(String::new()),
+      _ => None,
+    })
+  };
+  let number = |name: &str, default: usize| match value(name) {
+    Some(v) => v
+      .parse::<usize>()
+      .unwrap_or_else(|_| panic!("CRITICAL: {name} expects a number, got {v:?}")),
+    None => default,
+  };
+
+  value(&format!("--animate-day{day:02}"))?;
+  let every_nth = std::cmp::max(number("--animate-every", 1), 1);
+  let target = match value("--animate-ppm") {
+    Some(dir) => Target::PpmFrames {
+      dir: match dir.is_empty() {
+        true => PathBuf::from(format!("dumps/day{day:02}")),
+        false => PathBuf::from(dir),
+      },
+      scale: number("--animate-scale", 4),
+    },
+    None => Target::Terminal {
+      delay: Duration::from_millis(number("--animate-delay", 100) as u64),
+    },
+  };
+  Some((every_nth, target))
+}
+
+// =====================================================================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+  TogglePlay,
+  Step,
+  Back,
+  Quit,
+}
+
+// Line-based controls: <Enter> or `s` steps, `p` toggles play/pause, `b` steps back, `q` quits
+pub fn parse_command(line: &str) -> Option<Command> {
+  match line.trim() {
+    "" | "s" => Some(Command::Step),
+    "p" => Some(Command::TogglePlay),
+    "b" => Some(Command::Back),
+    "q" => Some(Command::Quit),
+    _ => None,
+  }
+}
+
+fn spawn_stdin_controls() -> Receiver<Command> {
+  let (sender, receiver) = mpsc::channel();
+  thread::spawn(move || {
+    for line in io::stdin().lock().lines() {
+      let Ok(line) = line else { break };
+      if let Some(command) = parse_command(&line) {
+        if sender.send(command).is_err() {
+          break;
+        }
+      }
+    }
+  });
+  receiver
+}
+
+// A single stdin reader for the whole process: the reader thread stays blocked on stdin after its player is done, so a
+// reader per player would swallow the next input line meant for the following one
+pub fn stdin_controls() -> &'static Mutex<Receiver<Command>> {
+  static CONTROLS: OnceLock<Mutex<Receiver<Command>>> = OnceLock::new();
+  CONTROLS.get_or_init(|| Mutex::new(spawn_stdin_controls()))
+}
+
+#[derive(Debug)]
+pub struct Player<'a, T> {
+  frames: &'a Vec<Frame<T>>,
+  delay: Duration,
+  current: usize,
+  playing: bool,
+  finished: bool,
+}
+
+impl<'a, T: Hash + Eq> Player<'a, T> {
+  pub fn new(frames: &'a Vec<Frame<T>>, delay: Duration) -> Self {
+    Self {
+      frames,
+      delay,
+      current: 0,
+      playing: false,
+      finished: frames.is_empty(),
+    }
+  }
+
+  pub fn current(&self) -> usize {
+    self.current
+  }
+
+  pub fn is_playing(&self) -> bool {
+    self.playing
+  }
+
+  pub fn is_finished(&self) -> bool {
+    self.finished
+  }
+
+  pub fn apply(&mut self, command: Command) {
+    match command {
+      Command::TogglePlay => self.playing = !self.playing,
+      Command::Step => {
+        self.playing = false;
+        self.advance();
+      }
+      Command::Back => {
+        self.playing = false;
+        self.current = self.current.saturating_sub(1);
+      }
+      Command::Quit => self.finished = true,
+    }
+  }
+
+  fn advance(&mut self) {
+    match self.current + 1 < self.frames.len() {
+      true => self.current += 1,
+      // ## stay on the last frame, pausing the playback
+      false => self.playing = false,
+    }
+  }
+
+  fn draw<G, W>(&self, palette: &Palette<T>, glyph: &G, out: &mut W) -> io::Result<()>
+  where
+    G: Fn(&T) -> char,
+    W: Write,
+  {
+    let frame = &self.frames[self.current];
+    let status = match self.playing {
+      true => "playing",
+      false => "paused",
+    };
+    write!(out, "\x1b[2J\x1b[H")?;
+    writeln!(
+      out,
+      "Frame {}/{} (step {}) [{status}] <Enter>/s: step, p: play/pause, b: back, q: quit",
+      self.current + 1,
+      self.frames.len(),
+      frame.step
+    )?;
+    write!(out, "{}", render::to_ansi(&frame.grid, palette, glyph))?;
+    out.flush()
+  }
+
+  pub fn run<G, W>(
+    &mut self,
+    palette: &Palette<T>,
+    glyph: G,
+    commands: &Receiver<Command>,
+    out: &mut W,
+  ) -> io::Result<()>
+  where
+    G: Fn(&T) -> char,
+    W: Write,
+  {
+    while !self.finished {
+      self.draw(palette, &glyph, out)?;
+
+      match self.playing {
+        true => {
+          thread::sleep(self.delay);
+          match commands.try_recv() {
+            Ok(command) => self.apply(command),
+            Err(TryRecvError::Empty) => self.advance(),
+            Err(TryRecvError::Disconnected) => self.advance(),
+          }
+        }
+        false => match commands.recv() {
+          Ok(command) => self.apply(command),
+          Err(_) => self.finished = true,
+        },
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn frame(step: usize) -> Frame<char> {
+    Frame {
+      step,
+      grid: vec![vec!['.']],
+    }
+  }
+
+  #[test]
+  fn test_recorder_keeps_every_nth_step() {
+    let mut recorder: Recorder<char> = Recorder::new(3);
+    let mut rendered = 0;
+    for step in 0..10 {
+      recorder.capture(step, || {
+        rendered += 1;
+        vec![vec!['.']]
+      });
+    }
+    recorder.capture_last(10, || vec![vec!['.']]);
+
+    let steps: Vec<usize> = recorder.frames().iter().map(|f| f.step).collect();
+    assert_eq!(steps, vec![0, 3, 6, 9, 10]);
+    assert_eq!(rendered, 4);
+
+    let mut disabled: Recorder<char> = Recorder::disabled();
+    disabled.capture(0, || panic!("disabled recorder must not render frames"));
+    disabled.capture_last(0, || panic!("disabled recorder must not render frames"));
+    assert!(disabled.frames().is_empty());
+  }
+
+  #[test]
+  fn test_recorder_writes_ppm_frames_as_recorded() {
+    let dir = std::env::temp_dir().join(format!("aoc-2024-frames-{}", std::process::id()));
+    let palette = Palette::new().with('.', render::Rgb::BLACK);
+    let mut recorder: Recorder<char> = Recorder::writing_ppm_frames(2, &dir, &palette, 1).unwrap();
+
+    recorder.capture(0, || vec![vec!['.']]);
+    assert!(dir.join("frame_000000.ppm").exists());
+    recorder.capture(1, || vec![vec!['.']]);
+    recorder.capture(2, || vec![vec!['.']]);
+    recorder.capture_last(3, || vec![vec!['.']]);
+    assert!(recorder.frames().is_empty());
+
+    let target = Target::PpmFrames {
+      dir: dir.clone(),
+      scale: 1,
+    };
+    recorder.emit(&palette, |&c| c, &target).unwrap();
+
+    let mut written: Vec<String> = fs::read_dir(&dir)
+      .unwrap()
+      .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+      .collect();
+    written.sort();
+    assert_eq!(written, vec!["frame_000000.ppm", "frame_000002.ppm", "frame_000003.ppm"]);
+    fs::remove_dir_all(dir).unwrap();
+  }
+
+  #[test]
+  fn test_player_controls() {
+    let frames = vec![frame(0), frame(5), frame(10)];
+    let mut player = Player::new(&frames, Duration::ZERO);
+
+    player.apply(Command::Step);
+    player.apply(Command::Step);
+    player.apply(Command::Step);
+    assert_eq!(player.current(), 2);
+
+    player.apply(Command::Back);
+    assert_eq!(player.current(), 1);
+
+    player.apply(Command::TogglePlay);
+    assert!(player.is_playing());
+    player.apply(Command::Step);
+    assert!(!player.is_playing());
+
+    player.apply(Command::Quit);
+    assert!(player.is_finished());
+  }
+
+  #[test]
+  fn test_player_run() {
+    let frames = vec![frame(0), frame(1), frame(2)];
+    let (sender, receiver) = mpsc::channel();
+    [Command::TogglePlay, Command::Quit]
+      .into_iter()
+      .for_each(|c| sender.send(c).unwrap());
+
+    let mut player = Player::new(&frames, Duration::ZERO);
+    let mut out: Vec<u8> = vec![];
+    player.run(&Palette::new(), |&c| c, &receiver, &mut out).unwrap();
+
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("Frame 1/3 (step 0) [paused]"));
+    assert!(out.contains("[playing]"));
+    assert!(player.is_finished());
+  }
+
+  #[test]
+  fn test_parse_args() {
+    assert_eq!(parse_args(6, ["aoc-2024", "--animate-day14"]), None);
+    assert_eq!(
+      parse_args(6, ["--animate-day06"]),
+      Some((
+        1,
+        Target::Terminal {
+          delay: Duration::from_millis(100)
+        }
+      ))
+    );
+    assert_eq!(
+      parse_args(14, ["--animate-every=5", "--animate-day14", "--animate-delay=20"]),
+      Some((
+        5,
+        Target::Terminal {
+          delay: Duration::from_millis(20)
+        }
+      ))
+    );
+    assert_eq!(
+      parse_args(15, ["--animate-day15", "--animate-ppm"]),
+      Some((
+        1,
+        Target::PpmFrames {
+          dir: PathBuf::from("dumps/day15"),
+          scale: 4
+        }
+      ))
+    );
+    assert_eq!(
+      parse_args(
+        15,
+        [
+          "--animate-day15",
+          "--animate-ppm=out",
+          "--animate-scale=2",
+          "--animate-every=0"
+        ]
+      ),
+      Some((
+        1,
+        Target::PpmFrames {
+          dir: PathBuf::from("out"),
+          scale: 2
+        }
+      ))
+    );
+  }
+
+  #[test]
+  fn test_parse_command() {
+    assert_eq!(parse_command(""), Some(Command::Step));
+    assert_eq!(parse_command("p\n"), Some(Command::TogglePlay));
+    assert_eq!(parse_command("q"), Some(Command::Quit));
+    assert_eq!(parse_command("x"), None);
+  }
+}
@@ -0,0 +1,261 @@
+use std::ops::{BitAnd, BitOr, BitXor};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+// Growable bitset for dense small-domain membership; grows on insert, reads past the end are `false`
+#[derive(Debug, Clone, Default)]
+pub struct BitSet {
+  words: Vec<u64>,
+}
+
+impl BitSet {
+  pub fn new() -> Self {
+    Self { words: vec![] }
+  }
+
+  pub fn with_capacity(bits: usize) -> Self {
+    Self {
+      words: vec![0; bits.div_ceil(WORD_BITS)],
+    }
+  }
+
+  fn grow_to(&mut self, words_len: usize) {
+    if self.words.len() < words_len {
+      self.words.resize(words_len, 0);
+    }
+  }
+
+  pub fn insert(&mut self, idx: usize) -> bool {
+    let (word, bit) = (idx / WORD_BITS, idx % WORD_BITS);
+    self.grow_to(word + 1);
+    let was_set = self.words[word] & (1 << bit) != 0;
+    self.words[word] |= 1 << bit;
+    !was_set
+  }
+
+  pub fn remove(&mut self, idx: usize) -> bool {
+    let (word, bit) = (idx / WORD_BITS, idx % WORD_BITS);
+    match self.words.get_mut(word) {
+      Some(w) => {
+        let was_set = *w & (1 << bit) != 0;
+        *w &= !(1 << bit);
+        was_set
+      }
+      None => false,
+    }
+  }
+
+  pub fn contains(&self, idx: usize) -> bool {
+    let (word, bit) = (idx / WORD_BITS, idx % WORD_BITS);
+    self.words.get(word).is_some_and(|w| w & (1 << bit) != 0)
+  }
+
+  pub fn count(&self) -> usize {
+    self.words.iter().map(|w| w.count_ones() as usize).sum()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.words.iter().all(|&w| w == 0)
+  }
+
+  pub fn clear(&mut self) {
+    self.words.iter_mut().for_each(|w| *w = 0);
+  }
+
+  pub fn union_with(&mut self, other: &BitSet) {
+    self.grow_to(other.words.len());
+    self.words.iter_mut().zip(&other.words).for_each(|(w, o)| *w |= o);
+  }
+
+  pub fn intersect_with(&mut self, other: &BitSet) {
+    self.words.truncate(other.words.len());
+    self.words.iter_mut().zip(&other.words).for_each(|(w, o)| *w &= o);
+  }
+
+  pub fn symmetric_difference_with(&mut self, other: &BitSet) {
+    self.grow_to(other.words.len());
+    self.words.iter_mut().zip(&other.words).for_each(|(w, o)| *w ^= o);
+  }
+
+  pub fn difference_with(&mut self, other: &BitSet) {
+    self.words.iter_mut().zip(&other.words).for_each(|(w, o)| *w &= !o);
+  }
+
+  pub fn is_subset(&self, other: &BitSet) -> bool {
+    self
+      .words
+      .iter()
+      .enumerate()
+      .all(|(i, w)| w & !other.words.get(i).unwrap_or(&0) == 0)
+  }
+
+  pub fn intersection_count(&self, other: &BitSet) -> usize {
+    self
+      .words
+      .iter()
+      .zip(&other.words)
+      .map(|(w, o)| (w & o).count_ones() as usize)
+      .sum()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+    self.words.iter().enumerate().flat_map(|(word_idx, &word)| {
+      let mut w = word;
+      std::iter::from_fn(move || match w {
+        0 => None,
+        _ => {
+          let bit = w.trailing_zeros() as usize;
+          w &= w - 1;
+          Some(word_idx * WORD_BITS + bit)
+        }
+      })
+    })
+  }
+}
+
+impl PartialEq for BitSet {
+  fn eq(&self, other: &BitSet) -> bool {
+    let len = self.words.len().max(other.words.len());
+    (0..len).all(|i| self.words.get(i).unwrap_or(&0) == other.words.get(i).unwrap_or(&0))
+  }
+}
+
+impl Eq for BitSet {}
+
+impl FromIterator<usize> for BitSet {
+  fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+    let mut bitset = BitSet::new();
+    iter.into_iter().for_each(|idx| {
+      bitset.insert(idx);
+    });
+    bitset
+  }
+}
+
+impl BitAnd for &BitSet {
+  type Output = BitSet;
+
+  fn bitand(self, other: &BitSet) -> BitSet {
+    let mut result = self.clone();
+    result.intersect_with(other);
+    result
+  }
+}
+
+impl BitOr for &BitSet {
+  type Output = BitSet;
+
+  fn bitor(self, other: &BitSet) -> BitSet {
+    let mut result = self.clone();
+    result.union_with(other);
+    result
+  }
+}
+
+impl BitXor for &BitSet {
+  type Output = BitSet;
+
+  fn bitxor(self, other: &BitSet) -> BitSet {
+    let mut result = self.clone();
+    result.symmetric_difference_with(other);
+    result
+  }
+}
+
+// =====================================================================================================================
+// ## Encoders mapping puzzle domain values onto dense bitset indices
+
+pub const TWO_LETTERS_DOMAIN: usize = 26 * 26;
+
+// Two lowercase letters (e.g. day23 computer names `ka`, `tc`) => 0..676
+pub fn encode_two_letters(name: &str) -> usize {
+  let bytes = name.as_bytes();
+  assert!(
+    bytes.len() == 2 && bytes.iter().all(|b| b.is_ascii_lowercase()),
+    "CRITICAL: expected two lowercase letters, got {name:?}"
+  );
+  (bytes[0] - b'a') as usize * 26 + (bytes[1] - b'a') as usize
+}
+
+pub fn decode_two_letters(idx: usize) -> String {
+  let (first, second) = ((idx / 26) as u8 + b'a', (idx % 26) as u8 + b'a');
+  String::from_utf8(vec![first, second]).unwrap()
+}
+
+// Grid cell => row-major index
+pub fn encode_grid_cell(i: usize, j: usize, cols: usize) -> usize {
+  i * cols + j
+}
+
+pub fn decode_grid_cell(idx: usize, cols: usize) -> (usize, usize) {
+  (idx / cols, idx % cols)
+}
+
+// Four price diffs, each within -9..=9 (day22) => 0..19^4
+pub const DIFF_SEQ_DOMAIN: usize = 19 * 19 * 19 * 19;
+
+pub fn encode_diff_seq(seq: (i8, i8, i8, i8)) -> usize {
+  [seq.0, seq.1, seq.2, seq.3].into_iter().fold(0, |acc, diff| {
+    assert!((-9..=9).contains(&diff), "CRITICAL: diff {diff} out of -9..=9 range");
+    acc * 19 + (diff + 9) as usize
+  })
+}
+
+pub fn decode_diff_seq(idx: usize) -> (i8, i8, i8, i8) {
+  let digit = |pos: u32| (idx / 19_usize.pow(pos) % 19) as i8 - 9;
+  (digit(3), digit(2), digit(1), digit(0))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_insert_remove_contains() {
+    let mut bitset = BitSet::new();
+    assert!(bitset.insert(3));
+    assert!(bitset.insert(130));
+    assert!(!bitset.insert(3));
+    assert!(bitset.contains(3));
+    assert!(bitset.contains(130));
+    assert!(!bitset.contains(4));
+    assert!(!bitset.contains(10_000));
+    assert_eq!(bitset.count(), 2);
+
+    assert!(bitset.remove(3));
+    assert!(!bitset.remove(3));
+    assert!(!bitset.remove(10_000));
+    assert_eq!(bitset.iter().collect::<Vec<_>>(), vec![130]);
+  }
+
+  #[test]
+  fn test_set_operations() {
+    let a: BitSet = [1, 2, 3, 64, 200].into_iter().collect();
+    let b: BitSet = [2, 3, 4, 65].into_iter().collect();
+
+    assert_eq!((&a & &b).iter().collect::<Vec<_>>(), vec![2, 3]);
+    assert_eq!((&a | &b).iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 64, 65, 200]);
+    assert_eq!((&a ^ &b).iter().collect::<Vec<_>>(), vec![1, 4, 64, 65, 200]);
+    assert_eq!(a.intersection_count(&b), 2);
+
+    let mut diff = a.clone();
+    diff.difference_with(&b);
+    assert_eq!(diff.iter().collect::<Vec<_>>(), vec![1, 64, 200]);
+    assert!(diff.is_subset(&a));
+    assert!(!a.is_subset(&b));
+    assert_eq!(&a & &b, [3, 2].into_iter().collect::<BitSet>());
+  }
+
+  #[test]
+  fn test_encoders() {
+    assert_eq!(encode_two_letters("aa"), 0);
+    assert_eq!(encode_two_letters("zz"), TWO_LETTERS_DOMAIN - 1);
+    assert_eq!(decode_two_letters(encode_two_letters("tc")), "tc");
+
+    assert_eq!(decode_grid_cell(encode_grid_cell(3, 7, 10), 10), (3, 7));
+
+    assert_eq!(encode_diff_seq((-9, -9, -9, -9)), 0);
+    assert_eq!(encode_diff_seq((9, 9, 9, 9)), DIFF_SEQ_DOMAIN - 1);
+    assert_eq!(decode_diff_seq(encode_diff_seq((-2, 1, -1, 3))), (-2, 1, -1, 3));
+  }
+}
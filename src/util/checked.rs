@@ -0,0 +1,152 @@
+use std::any::type_name;
+use std::fmt;
+use std::num::{IntErrorKind, ParseIntError};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArithmeticError {
+  Overflow(String),
+  DivisionByZero(String),
+  Conversion(String),
+  InvalidNumber(String),
+}
+
+impl fmt::Display for ArithmeticError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ArithmeticError::Overflow(expr) => write!(f, "arithmetic overflow: {expr}"),
+      ArithmeticError::DivisionByZero(expr) => write!(f, "division by zero: {expr}"),
+      ArithmeticError::Conversion(expr) => write!(f, "lossy conversion: {expr}"),
+      ArithmeticError::InvalidNumber(expr) => write!(f, "invalid number: {expr}"),
+    }
+  }
+}
+
+impl std::error::Error for ArithmeticError {}
+
+// Integer arithmetic that reports overflow as an error instead of wrapping (release) or panicking (debug)
+pub trait CheckedOps: Sized + Copy + fmt::Display {
+  fn try_add(self, rhs: Self) -> Result<Self, ArithmeticError>;
+  fn try_sub(self, rhs: Self) -> Result<Self, ArithmeticError>;
+  fn try_mul(self, rhs: Self) -> Result<Self, ArithmeticError>;
+  fn try_div(self, rhs: Self) -> Result<Self, ArithmeticError>;
+  fn try_rem(self, rhs: Self) -> Result<Self, ArithmeticError>;
+}
+
+macro_rules! impl_checked_ops {
+  ($($t:ty),*) => {
+    $(
+      impl CheckedOps for $t {
+        fn try_add(self, rhs: Self) -> Result<Self, ArithmeticError> {
+          self
+            .checked_add(rhs)
+            .ok_or_else(|| ArithmeticError::Overflow(format!("{self} + {rhs} ({})", type_name::<Self>())))
+        }
+
+        fn try_sub(self, rhs: Self) -> Result<Self, ArithmeticError> {
+          self
+            .checked_sub(rhs)
+            .ok_or_else(|| ArithmeticError::Overflow(format!("{self} - {rhs} ({})", type_name::<Self>())))
+        }
+
+        fn try_mul(self, rhs: Self) -> Result<Self, ArithmeticError> {
+          self
+            .checked_mul(rhs)
+            .ok_or_else(|| ArithmeticError::Overflow(format!("{self} * {rhs} ({})", type_name::<Self>())))
+        }
+
+        fn try_div(self, rhs: Self) -> Result<Self, ArithmeticError> {
+          match rhs {
+            0 => Err(ArithmeticError::DivisionByZero(format!("{self} / {rhs}"))),
+            _ => self
+              .checked_div(rhs)
+              .ok_or_else(|| ArithmeticError::Overflow(format!("{self} / {rhs} ({})", type_name::<Self>()))),
+          }
+        }
+
+        fn try_rem(self, rhs: Self) -> Result<Self, ArithmeticError> {
+          match rhs {
+            0 => Err(ArithmeticError::DivisionByZero(format!("{self} % {rhs}"))),
+            _ => self
+              .checked_rem(rhs)
+              .ok_or_else(|| ArithmeticError::Overflow(format!("{self} % {rhs} ({})", type_name::<Self>()))),
+          }
+        }
+      }
+    )*
+  };
+}
+
+impl_checked_ops!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+pub fn try_sum<T, I>(items: I) -> Result<T, ArithmeticError>
+where
+  T: CheckedOps + Default,
+  I: IntoIterator<Item = T>,
+{
+  items.into_iter().try_fold(T::default(), |acc, item| acc.try_add(item))
+}
+
+pub fn try_convert<T, U>(value: T) -> Result<U, ArithmeticError>
+where
+  T: Copy + fmt::Display,
+  U: TryFrom<T>,
+{
+  U::try_from(value)
+    .map_err(|_| ArithmeticError::Conversion(format!("{value} ({} -> {})", type_name::<T>(), type_name::<U>())))
+}
+
+// Parses an integer, reporting out-of-range values as overflow instead of a generic parse error
+pub fn try_parse<T>(value: &str) -> Result<T, ArithmeticError>
+where
+  T: FromStr<Err = ParseIntError>,
+{
+  value.parse::<T>().map_err(|e| match e.kind() {
+    IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+      ArithmeticError::Overflow(format!("{value} ({})", type_name::<T>()))
+    }
+    _ => ArithmeticError::InvalidNumber(format!("{value:?} ({})", type_name::<T>())),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_checked_ops() {
+    assert_eq!(2_u32.try_add(3), Ok(5));
+    assert_eq!(7_i64.try_sub(10), Ok(-3));
+    assert_eq!(6_u16.try_mul(7), Ok(42));
+    assert_eq!(7_i64.try_div(2), Ok(3));
+    assert_eq!(7_i64.try_rem(2), Ok(1));
+
+    assert_eq!(u32::MAX.try_add(1), Err(ArithmeticError::Overflow(String::from("4294967295 + 1 (u32)"))));
+    assert!(0_u8.try_sub(1).is_err());
+    assert!(i64::MAX.try_mul(2).is_err());
+    assert!(i64::MIN.try_div(-1).is_err());
+    assert_eq!(1_i64.try_div(0), Err(ArithmeticError::DivisionByZero(String::from("1 / 0"))));
+  }
+
+  #[test]
+  fn test_try_sum() {
+    assert_eq!(try_sum(vec![1_u8, 2, 3]), Ok(6));
+    assert!(try_sum(vec![200_u8, 100]).is_err());
+  }
+
+  #[test]
+  fn test_try_convert() {
+    assert_eq!(try_convert::<usize, u16>(65535), Ok(65535_u16));
+    assert!(try_convert::<usize, u16>(65536).is_err());
+    assert!(try_convert::<i64, usize>(-1).is_err());
+  }
+
+  #[test]
+  fn test_try_parse() {
+    assert_eq!(try_parse::<u8>("255"), Ok(255));
+    assert_eq!(try_parse::<i64>("-12"), Ok(-12));
+    assert_eq!(try_parse::<u8>("256"), Err(ArithmeticError::Overflow(String::from("256 (u8)"))));
+    assert!(matches!(try_parse::<i64>("99999999999999999999"), Err(ArithmeticError::Overflow(_))));
+    assert_eq!(try_parse::<u8>("x1"), Err(ArithmeticError::InvalidNumber(String::from("\"x1\" (u8)"))));
+  }
+}
@@ -0,0 +1,192 @@
+// Disjoint-set (union-find) with path compression and union by rank
+#[derive(Debug, Clone)]
+pub struct Dsu {
+  parent: Vec<usize>,
+  rank: Vec<u8>,
+  size: Vec<usize>,
+  sets_count: usize,
+}
+
+impl Dsu {
+  pub fn new(n: usize) -> Self {
+    Self {
+      parent: (0..n).collect(),
+      rank: vec![0; n],
+      size: vec![1; n],
+      sets_count: n,
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.parent.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.parent.is_empty()
+  }
+
+  pub fn sets_count(&self) -> usize {
+    self.sets_count
+  }
+
+  pub fn find(&mut self, x: usize) -> usize {
+    // ## iterative, so that long chains can't overflow the stack
+    let mut root = x;
+    while self.parent[root] != root {
+      root = self.parent[root];
+    }
+
+    let mut node = x;
+    while self.parent[node] != root {
+      let next = self.parent[node];
+      self.parent[node] = root;
+      node = next;
+    }
+    root
+  }
+
+  pub fn union(&mut self, a: usize, b: usize) -> bool {
+    let (root_a, root_b) = (self.find(a), self.find(b));
+    if root_a == root_b {
+      return false;
+    }
+
+    let (root, child) = match self.rank[root_a].cmp(&self.rank[root_b]) {
+      std::cmp::Ordering::Less => (root_b, root_a),
+      std::cmp::Ordering::Greater => (root_a, root_b),
+      std::cmp::Ordering::Equal => {
+        self.rank[root_a] += 1;
+        (root_a, root_b)
+      }
+    };
+
+    self.parent[child] = root;
+    self.size[root] += self.size[child];
+    self.sets_count -= 1;
+    true
+  }
+
+  pub fn connected(&mut self, a: usize, b: usize) -> bool {
+    self.find(a) == self.find(b)
+  }
+
+  pub fn set_size(&mut self, x: usize) -> usize {
+    let root = self.find(x);
+    self.size[root]
+  }
+
+  // Dense set labels (0..sets_count), numbered in order of the first element of each set
+  pub fn labels(&mut self) -> Vec<usize> {
+    let mut root_labels: Vec<Option<usize>> = vec![None; self.len()];
+    let mut next_label = 0;
+
+    (0..self.len())
+      .map(|x| {
+        let root = self.find(x);
+        *root_labels[root].get_or_insert_with(|| {
+          next_label += 1;
+          next_label - 1
+        })
+      })
+      .collect()
+  }
+}
+
+// =====================================================================================================================
+
+// Joins every cell of the grid with its east and south neighbour when `is_joined` holds for the pair.
+// Cells are indexed row-major, i.e. `row_idx * cols + col_idx`.
+pub fn grid_dsu<T, F>(grid: &Vec<Vec<T>>, is_joined: F) -> Dsu
+where
+  F: Fn(&T, &T) -> bool,
+{
+  let (rows, cols) = (grid.len(), grid.first().map_or(0, |row| row.len()));
+  let mut dsu = Dsu::new(rows * cols);
+
+  for i in 0..rows {
+    for j in 0..cols {
+      let idx = i * cols + j;
+      if j + 1 < cols && is_joined(&grid[i][j], &grid[i][j + 1]) {
+        dsu.union(idx, idx + 1);
+      }
+      if i + 1 < rows && is_joined(&grid[i][j], &grid[i + 1][j]) {
+        dsu.union(idx, idx + cols);
+      }
+    }
+  }
+  dsu
+}
+
+// Labels 4-connected components of the grid, joining neighbours for which `is_joined` holds
+pub fn label_grid_components<T, F>(grid: &Vec<Vec<T>>, is_joined: F) -> Vec<Vec<usize>>
+where
+  F: Fn(&T, &T) -> bool,
+{
+  let cols = grid.first().map_or(0, |row| row.len());
+  let labels = grid_dsu(grid, is_joined).labels();
+
+  match cols {
+    0 => vec![],
+    _ => labels.chunks(cols).map(|row| row.to_vec()).collect(),
+  }
+}
+
+// Labels 4-connected components made of equal cell values
+pub fn label_grid_regions<T: PartialEq>(grid: &Vec<Vec<T>>) -> Vec<Vec<usize>> {
+  label_grid_components(grid, |a, b| a == b)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_union_find() {
+    let mut dsu = Dsu::new(6);
+    assert_eq!(dsu.sets_count(), 6);
+
+    assert!(dsu.union(0, 1));
+    assert!(dsu.union(1, 2));
+    assert!(dsu.union(3, 4));
+    assert!(!dsu.union(2, 0));
+
+    assert!(dsu.connected(0, 2));
+    assert!(!dsu.connected(0, 3));
+    assert_eq!(dsu.set_size(2), 3);
+    assert_eq!(dsu.set_size(4), 2);
+    assert_eq!(dsu.set_size(5), 1);
+    assert_eq!(dsu.sets_count(), 3);
+    assert_eq!(dsu.labels(), vec![0, 0, 0, 1, 1, 2]);
+  }
+
+  #[test]
+  fn test_long_chain_does_not_overflow() {
+    let n = 1_000_000;
+    let mut dsu = Dsu::new(n);
+    (1..n).for_each(|i| {
+      dsu.union(i - 1, i);
+    });
+    assert_eq!(dsu.sets_count(), 1);
+    assert_eq!(dsu.set_size(0), n);
+  }
+
+  #[test]
+  fn test_label_grid_regions() {
+    let grid: Vec<Vec<char>> = ["AAAA", "BBCD", "BBCC", "EEEC"]
+      .into_iter()
+      .map(|l| l.chars().collect())
+      .collect();
+
+    let labels = label_grid_regions(&grid);
+    #[rustfmt::skip]
+    assert_eq!(
+      labels,
+      vec![
+        vec![0, 0, 0, 0],
+        vec![1, 1, 2, 3],
+        vec![1, 1, 2, 2],
+        vec![4, 4, 4, 2],
+      ]
+    );
+  }
+}
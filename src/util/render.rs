@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+  pub const BLACK: Rgb = Rgb(0, 0, 0);
+  pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+  fn is_dark(&self) -> bool {
+    // ## perceived brightness, used to pick a readable glyph colour on top of the cell colour
+    (299 * self.0 as u32 + 587 * self.1 as u32 + 114 * self.2 as u32) / 1000 < 128
+  }
+}
+
+// Cell value => colour mapping; values without an explicit colour get a fallback colour or,
+// if none was set, a stable colour derived from the value's hash
+#[derive(Debug, Clone)]
+pub struct Palette<T> {
+  colors: HashMap<T, Rgb>,
+  fallback: Option<Rgb>,
+}
+
+impl<T: Hash + Eq> Default for Palette<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Hash + Eq> Palette<T> {
+  pub fn new() -> Self {
+    Self {
+      colors: HashMap::new(),
+      fallback: None,
+    }
+  }
+
+  pub fn with(mut self, value: T, color: Rgb) -> Self {
+    self.colors.insert(value, color);
+    self
+  }
+
+  pub fn with_fallback(mut self, color: Rgb) -> Self {
+    self.fallback = Some(color);
+    self
+  }
+
+  pub fn color(&self, value: &T) -> Rgb {
+    match (self.colors.get(value), self.fallback) {
+      (Some(&color), _) => color,
+      (None, Some(fallback)) => fallback,
+      (None, None) => hashed_color(value),
+    }
+  }
+}
+
+// 64-bit FNV-1a: unlike `DefaultHasher`, its output is specified, so a value keeps its colour across Rust releases
+struct FnvHasher(u64);
+
+impl Hasher for FnvHasher {
+  fn finish(&self) -> u64 {
+    self.0
+  }
+
+  fn write(&mut self, bytes: &[u8]) {
+    bytes.iter().for_each(|&b| {
+      self.0 ^= b as u64;
+      self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+    });
+  }
+}
+
+fn hashed_color<T: Hash>(value: &T) -> Rgb {
+  let mut hasher = FnvHasher(0xcbf2_9ce4_8422_2325);
+  value.hash(&mut hasher);
+  let h = hasher.finish();
+  // ## keep channels away from pure black so the cells stay distinguishable from the background
+  let channel = |shift: u32| 64 + ((h >> shift) & 0xff) as u8 % 192;
+  Rgb(channel(0), channel(8), channel(16))
+}
+
+// Palette for the `#`/`.` char maps most days use
+pub fn char_map_palette() -> Palette<char> {
+  Palette::new().with('#', Rgb(128, 128, 128)).with('.', Rgb(16, 16, 16))
+}
+
+// =====================================================================================================================
+
+pub fn to_ansi<T, G>(grid: &[Vec<T>], palette: &Palette<T>, glyph: G) -> String
+where
+  T: Hash + Eq,
+  G: Fn(&T) -> char,
+{
+  let mut output = String::new();
+  grid.iter().for_each(|row| {
+    row.iter().for_each(|cell| {
+      let bg = palette.color(cell);
+      let fg = match bg.is_dark() {
+        true => Rgb::WHITE,
+        false => Rgb::BLACK,
+      };
+      output.push_str(&format!(
+        "\x1b[48;2;{};{};{}m\x1b[38;2;{};{};{}m{}",
+        bg.0,
+        bg.1,
+        bg.2,
+        fg.0,
+        fg.1,
+        fg.2,
+        glyph(cell)
+      ));
+    });
+    output.push_str("\x1b[0m\n");
+  });
+  output
+}
+
+// Binary PPM (P6), every cell drawn as a `scale` x `scale` square
+pub fn to_ppm<T: Hash + Eq>(grid: &[Vec<T>], palette: &Palette<T>, scale: usize) -> Vec<u8> {
+  let (rows, cols) = (grid.len(), grid.first().map_or(0, |row| row.len()));
+  let (width, height) = (cols * scale, rows * scale);
+
+  let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+  image.reserve(width * height * 3);
+  grid.iter().for_each(|row| {
+    let pixel_row: Vec<u8> = row
+      .iter()
+      .flat_map(|cell| {
+        let color = palette.color(cell);
+        [color.0, color.1, color.2].repeat(scale)
+      })
+      .collect();
+    (0..scale).for_each(|_| image.extend_from_slice(&pixel_row));
+  });
+  image
+}
+
+#[derive(Debug, Clone)]
+pub enum Output {
+  Terminal,
+  Ppm { path: PathBuf, scale: usize },
+}
+
+pub fn dump<T, G>(grid: &[Vec<T>], palette: &Palette<T>, glyph: G, output: &Output) -> io::Result<()>
+where
+  T: Hash + Eq,
+  G: Fn(&T) -> char,
+{
+  dump_to(grid, palette, glyph, output, &mut io::stdout())
+}
+
+// Same as `dump`, with the terminal output going to `terminal` instead of stdout
+fn dump_to<T, G, W>(
+  grid: &[Vec<T>],
+  palette: &Palette<T>,
+  glyph: G,
+  output: &Output,
+  terminal: &mut W,
+) -> io::Result<()>
+where
+  T: Hash + Eq,
+  G: Fn(&T) -> char,
+  W: Write,
+{
+  match output {
+    Output::Terminal => write!(terminal, "{}", to_ansi(grid, palette, glyph)),
+    Output::Ppm { path, scale } => {
+      if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+      }
+      fs::write(path, to_ppm(grid, palette, *scale))
+    }
+  }
+}
+
+pub fn dump_chars(grid: &[Vec<char>], palette: &Palette<char>, output: &Output) -> io::Result<()> {
+  dump(grid, palette, |&c| c, output)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_palette() {
+    let palette = Palette::new().with('#', Rgb::WHITE);
+    assert_eq!(palette.color(&'#'), Rgb::WHITE);
+    assert_eq!(palette.color(&'a'), palette.color(&'a'));
+    assert_ne!(palette.color(&'a'), Rgb::BLACK);
+
+    // ## hashed colours are pinned, they must not change with the toolchain
+    assert_eq!(palette.color(&'a'), Rgb(196, 105, 143));
+
+    let palette = palette.with_fallback(Rgb::BLACK);
+    assert_eq!(palette.color(&'a'), Rgb::BLACK);
+  }
+
+  #[test]
+  fn test_to_ppm() {
+    let grid = vec![vec!['#', '.'], vec!['.', '#']];
+    let palette = Palette::new().with('#', Rgb::WHITE).with('.', Rgb::BLACK);
+
+    let image = to_ppm(&grid, &palette, 2);
+    let header = b"P6\n4 4\n255\n";
+    assert_eq!(&image[..header.len()], header);
+
+    let pixels = &image[header.len()..];
+    assert_eq!(pixels.len(), 4 * 4 * 3);
+    assert_eq!(&pixels[0..6], &[255; 6]); // ## row 0: `#` is two pixels wide...
+    assert_eq!(&pixels[6..12], &[0; 6]); // ## ... followed by two `.` pixels
+    assert_eq!(&pixels[24..30], &[0; 6]); // ## row 2 starts with the second grid row
+  }
+
+  #[test]
+  fn test_to_ansi() {
+    let grid = vec![vec!['#', '.']];
+    let palette = Palette::new().with('#', Rgb::WHITE).with('.', Rgb::BLACK);
+
+    let ansi = to_ansi(&grid, &palette, |&c| c);
+    assert_eq!(ansi, "\x1b[48;2;255;255;255m\x1b[38;2;0;0;0m#\x1b[48;2;0;0;0m\x1b[38;2;255;255;255m.\x1b[0m\n");
+  }
+
+  #[test]
+  fn test_dump_terminal() {
+    let grid = vec![vec!['#', '.'], vec!['.', '#']];
+    let palette = char_map_palette();
+
+    let mut out: Vec<u8> = vec![];
+    dump_to(&grid, &palette, |&c| c, &Output::Terminal, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), to_ansi(&grid, &palette, |&c| c));
+  }
+
+  #[test]
+  fn test_dump_ppm() {
+    let grid = vec![vec!['#', '.'], vec!['.', '#']];
+    let palette = char_map_palette();
+    let dir = std::env::temp_dir().join(format!("aoc-2024-render-{}", std::process::id()));
+    let path = dir.join("nested").join("grid.ppm");
+
+    let mut out: Vec<u8> = vec![];
+    dump_to(
+      &grid,
+      &palette,
+      |&c| c,
+      &Output::Ppm {
+        path: path.clone(),
+        scale: 3,
+      },
+      &mut out,
+    )
+    .unwrap();
+    assert!(out.is_empty());
+    assert_eq!(fs::read(&path).unwrap(), to_ppm(&grid, &palette, 3));
+    fs::remove_dir_all(dir).unwrap();
+  }
+}